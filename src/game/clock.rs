@@ -0,0 +1,52 @@
+//! `clock` defines the `GameClock` type which tracks the passing of periods in the game.
+//!
+//! #Last Modified
+//!
+//! Author: Daniel Bechaz</br>
+//! Date: 2017/11/10
+
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+/// `GameClock` counts the periods which have passed in the game.
+pub struct GameClock {
+    /// The number of periods which have passed.
+    period: u64
+}
+
+impl GameClock {
+    /// Returns a new `GameClock` at the first period.
+    pub const fn new() -> Self {
+        GameClock { period: 0 }
+    }
+    /// Moves this `GameClock` on to the next period.
+    pub fn advance(&mut self) {
+        self.period += 1
+    }
+    /// Returns the current period of this `GameClock`.
+    pub fn current_period(&self) -> u64 {
+        self.period
+    }
+}
+
+/// The `GameClock` of the running game.
+static GAME_CLOCK: Mutex<GameClock> = Mutex::new(GameClock::new());
+
+/// Locks and returns the `GameClock` of the running game.
+pub fn get_game_clock() -> MutexGuard<'static, GameClock> {
+    GAME_CLOCK.lock().expect("Game Clock Mutex Poisoned!!!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_advance() {
+        let mut clock = GameClock::new();
+        assert!(clock.current_period() == 0, "`GameClock::new` did not start at the first period.");
+        
+        (0..5).for_each(|_| clock.advance());
+        assert!(clock.current_period() == 5, "`GameClock::advance` failed to count periods.");
+    }
+}
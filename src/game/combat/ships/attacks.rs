@@ -7,13 +7,17 @@
 
 use game::*;
 use super::ShipSize;
+use super::weapon_error::*;
+use super::ship_template::ShipTemplate;
 use std::iter::Iterator;
 use std::cmp::Ordering;
+use std::fmt;
+use serde::{Deserialize, Deserializer, de};
 
 pub type DamagePoint = UInt;
 
 /// A `TargetedAttack` is an `Attack` with a smallest size of target allowed.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct TargetedAttack {
     /// The `Attack` for this `TargetedAttack`.
     pub attack: Attack,
@@ -34,6 +38,28 @@ impl TargetedAttack {
             smallest_target
         }
     }
+    /// Attempts to call `TargetedAttack::new` if parameters pass checks.
+    ///
+    /// #Params
+    ///
+    /// Refer to `TargetedAttack::new` for parameters.
+    ///
+    /// #Errors
+    ///
+    /// AttacksError --- attack.parralel_attacks == 0
+    /// DamageError --- attack.damage_per_attack == 0
+    pub fn checked(attack: Attack, smallest_target: ShipSize) -> Result<Self, WeaponError> {
+        //Check that there are attacks.
+        if attack.parralel_attacks == 0 {
+            Err(AttacksError)
+        //Check that the attacks do damage.
+        } else if attack.damage_per_attack == 0 {
+            Err(DamageError)
+        //All checks passed, parameters are valid.
+        } else {
+            Ok(Self::new(attack, smallest_target))
+        }
+    }
     /// Returns true if the passed size of target is a valid target for this
     /// `TargetedAttack`.
     ///
@@ -43,6 +69,18 @@ impl TargetedAttack {
     pub fn valid_target(&self, target_size: ShipSize) -> bool {
         self.smallest_target <= target_size
     }
+    /// Returns the damage this `TargetedAttack` deals against a target of the passed size.
+    ///
+    /// #Params
+    ///
+    /// target_size --- The size of the target in question.
+    pub fn damage_against(&self, target_size: ShipSize) -> DamagePoint {
+        if self.valid_target(target_size) {
+            self.attack.sum_damage()
+        } else {
+            0
+        }
+    }
     /// Returns true if the passed `TargetedAttack` has the same smallest target as this
     /// `TargetedAttack`.
     ///
@@ -64,8 +102,8 @@ impl Ord for TargetedAttack {
     fn cmp(&self, other: &Self) -> Ordering {
         //Ordering is done on the smallest target.
         match self.smallest_target.cmp(&other.smallest_target) {
-            //Equality is resolved by ordering damage per attack.
-            Ordering::Equal => match self.attack.damage_per_attack.cmp(&other.attack.damage_per_attack) {
+            //Equality is resolved by ordering how each attack hits.
+            Ordering::Equal => match self.attack.cmp_profile(&other.attack) {
                 //Equality is resolved by ordering parralel attacks.
                 Ordering::Equal => self.attack.parralel_attacks.cmp(&other.attack.parralel_attacks),
                 ord => ord
@@ -76,15 +114,49 @@ impl Ord for TargetedAttack {
 }
 
 /// An `Attack` is a number of parralel attack projectiles with a damage per attack.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Attack {
     /// The number of parralel attacks for this `Attack`.
     pub parralel_attacks: UInt,
     /// The damage dealt by each attack.
-    pub damage_per_attack: DamagePoint
+    pub damage_per_attack: DamagePoint,
+    /// The multiplier applied to `damage_per_attack` on a critical hit.
+    #[serde(default = "default_crit_multiplier")]
+    pub crit_multiplier: UInt,
+    /// The percentage chance of this `Attack` landing a critical hit.
+    #[serde(default, deserialize_with = "deserialize_crit_chance")]
+    pub crit_chance_percent: UInt,
+    /// Whether this `Attack` ignores shields and damages hull directly.
+    #[serde(default)]
+    pub bypass_shields: bool
+}
+
+/// The `crit_multiplier` of an `Attack` which does not specify one.
+fn default_crit_multiplier() -> UInt {
+    1
+}
+
+/// Deserializes a `crit_chance_percent`, rejecting chances over 100.
+fn deserialize_crit_chance<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UInt, D::Error> {
+    let crit_chance_percent = UInt::deserialize(deserializer)?;
+    
+    if crit_chance_percent > 100 {
+        Err(<D::Error as de::Error>::custom("the `crit_chance_percent` exceeds 100"))
+    } else {
+        Ok(crit_chance_percent)
+    }
 }
 
 impl Attack {
+    /// An `Attack` with no attacks which deals no damage.
+    pub const ZERO: Attack = Attack {
+        parralel_attacks: 0,
+        damage_per_attack: 0,
+        crit_multiplier: 1,
+        crit_chance_percent: 0,
+        bypass_shields: false
+    };
+    
     /// Creates a new `Attack` from parts.
     ///
     /// #Params
@@ -94,18 +166,54 @@ impl Attack {
     pub fn new(parralel_attacks: UInt, damage_per_attack: DamagePoint) -> Self {
         Self {
             parralel_attacks,
-            damage_per_attack
+            damage_per_attack,
+            crit_multiplier: default_crit_multiplier(),
+            crit_chance_percent: 0,
+            bypass_shields: false
+        }
+    }
+    /// Attempts to return this `Attack` with the passed chance of landing a critical hit.
+    ///
+    /// #Params
+    ///
+    /// crit_multiplier --- The multiplier applied to `damage_per_attack` on a critical hit.
+    /// crit_chance_percent --- The percentage chance of landing a critical hit.
+    ///
+    /// #Errors
+    ///
+    /// CritError --- crit_chance_percent > 100
+    pub fn with_crit(self, crit_multiplier: UInt, crit_chance_percent: UInt) -> Result<Self, WeaponError> {
+        //Check that the chance is a percentage.
+        if crit_chance_percent > 100 {
+            Err(CritError)
+        } else {
+            Ok(Self {
+                crit_multiplier,
+                crit_chance_percent,
+                ..self
+            })
         }
     }
-    /// Attempts to merge another `Attack` into this `Attack` if they deal the same
-    /// amount of damage per attack else it returns ownership of `other`.
+    /// Returns this `Attack` set to ignore shields or not.
+    ///
+    /// #Params
+    ///
+    /// bypass_shields --- Whether this `Attack` ignores shields.
+    pub fn with_bypass_shields(self, bypass_shields: bool) -> Self {
+        Self {
+            bypass_shields,
+            ..self
+        }
+    }
+    /// Attempts to merge another `Attack` into this `Attack` if each of their attacks hit
+    /// the same way else it returns ownership of `other`.
     ///
     /// #Params
     ///
     /// other --- The other `Attack` to merge into this one.
     pub fn merge(&mut self, other: Self) -> Option<Self> {
-        //If they deal the same damage per attack then they can be merged...
-        if self.same_damage(&other) {
+        //If each attack hits the same way then they can be merged...
+        if self.cmp_profile(&other) == Ordering::Equal {
             //Merging means adding the attacks from `other` into this `Attack`.
             self.parralel_attacks += other.parralel_attacks; None
         //Else they cannot be merged.
@@ -114,19 +222,37 @@ impl Attack {
             Some(other)
         }
     }
+    /// Returns true if this `Attack` has no attacks left.
+    pub fn is_spent(&self) -> bool {
+        self.parralel_attacks == 0
+    }
     /// Sums up all the damage dealt by each of the attacks of this `Attack`.
     pub fn sum_damage(&self) -> DamagePoint {
-        self.parralel_attacks * self.damage_per_attack
+        self.parralel_attacks.saturating_mul(self.damage_per_attack)
+    }
+    /// Returns the damage dealt by each attack of this `Attack` on a critical hit.
+    pub fn crit_damage(&self) -> DamagePoint {
+        self.damage_per_attack.saturating_mul(self.crit_multiplier)
     }
     /// Returns true if `other` deals the same damage per attack as this `Attack`.
     pub fn same_damage(&self, other: &Self) -> bool {
         self.damage_per_attack == other.damage_per_attack
     }
+    /// Orders this `Attack` against `other` by how each of their attacks hit, ignoring
+    /// the number of parralel attacks.
+    ///
+    /// #Params
+    ///
+    /// other --- The other `Attack` to compare against.
+    pub fn cmp_profile(&self, other: &Self) -> Ordering {
+        (self.damage_per_attack, self.crit_multiplier, self.crit_chance_percent, self.bypass_shields)
+        .cmp(&(other.damage_per_attack, other.crit_multiplier, other.crit_chance_percent, other.bypass_shields))
+    }
 }
 
 /// A collection of `TargetedAttack`s ordered by the size of their smallest target and
 /// without duplicates of smallest target.
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct ReducedAttacks {
     /// The `Vec` of `TargetedAttack`s.
     attacks: Vec<TargetedAttack>
@@ -171,15 +297,28 @@ impl ReducedAttacks {
             Self::from_parts(attacks)
         }
     }
+    /// Creates the `ReducedAttacks` produced in parralel by `count` Ships of the passed
+    /// `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// template --- The `ShipTemplate` of the attacking Ships.
+    /// count --- The number of attacking Ships.
+    pub fn from_template(template: &ShipTemplate, count: UInt) -> Self {
+        let mut attacks = template.get_attacks().clone();
+        //Large groups saturate rather than overflow.
+        attacks.iter_mut().for_each(|attack| attack.attack.parralel_attacks = attack.attack.parralel_attacks.saturating_mul(count));
+        attacks
+    }
     /// Add a `TargetedAttack` to this `ReducedAttacks`.
     ///
     /// #Params
     ///
     /// attack --- The `TargetedAttack` to add to this `TargetedAttack`.
     pub fn add_attack(&mut self, attack: TargetedAttack) {
-        //Search for an existing `TargetedAttack` with the same smallest target and damage per attack...
+        //Search for an existing `TargetedAttack` with the same smallest target which hits the same way...
         match self.attacks.binary_search_by(|existing| match existing.smallest_target.cmp(&attack.smallest_target) {
-                Ordering::Equal => existing.attack.damage_per_attack.cmp(&attack.attack.damage_per_attack),
+                Ordering::Equal => existing.attack.cmp_profile(&attack.attack),
                 ord => ord
             }) {
             //If a `TargetedAttack` exists then simply add `attack`s attacks too it...
@@ -204,8 +343,195 @@ impl ReducedAttacks {
     pub fn iter_mut(&mut self) -> ::std::slice::IterMut<TargetedAttack> {
         self.attacks.iter_mut()
     }
+    /// Returns the number of `TargetedAttack`s in this `ReducedAttacks`.
+    pub fn len(&self) -> usize {
+        self.attacks.len()
+    }
+    /// Returns true if there are no `TargetedAttack`s in this `ReducedAttacks`.
+    pub fn is_empty(&self) -> bool {
+        self.attacks.is_empty()
+    }
     /// Removes all of the `TargetedAttack`s which have no parralel attacks.
     pub fn clear_used_attacks(&mut self) {
-        self.attacks.retain(|attack| attack.attack.parralel_attacks != 0);
+        self.attacks.retain(|attack| !attack.attack.is_spent());
+    }
+}
+
+/// Renders a table of the `TargetedAttack`s, one row per `TargetedAttack`.
+impl fmt::Display for ReducedAttacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "smallest_target | parralel_attacks | damage_per_attack | total")?;
+        for attack in self.iter() {
+            writeln!(
+                f,
+                "{:>15} | {:>16} | {:>17} | {}",
+                attack.smallest_target,
+                attack.attack.parralel_attacks,
+                attack.attack.damage_per_attack,
+                attack.attack.sum_damage()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A source of randomness used when resolving combat.
+///
+/// An attack lands a critical hit when the roll is less than its
+/// `crit_chance_percent`.
+pub trait CombatRng {
+    /// Returns a random percentage in the range [0, 100).
+    fn roll_percent(&mut self) -> UInt;
+}
+
+/// A `CombatRng` which never rolls a critical hit.
+///
+/// It always rolls 100, outside the range of `CombatRng::roll_percent`, which no
+/// `crit_chance_percent` can exceed.
+pub struct NoCrits;
+
+impl CombatRng for NoCrits {
+    fn roll_percent(&mut self) -> UInt {
+        100
+    }
+}
+
+/// A `CombatRng` which always rolls the same percentage.
+#[cfg(test)]
+pub struct ForcedRng(pub UInt);
+
+#[cfg(test)]
+impl CombatRng for ForcedRng {
+    fn roll_percent(&mut self) -> UInt {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ship_template::test_template;
+    
+    #[test]
+    fn test_from_template() {
+        let template = test_template()
+            .attacks(ReducedAttacks::new(vec![
+                TargetedAttack::new(Attack::new(2, 10), 1),
+                TargetedAttack::new(Attack::new(1, 50), 3)
+            ]))
+            .build().expect("Failed to create template.");
+        
+        assert!(
+            ReducedAttacks::from_template(&template, 1) == *template.get_attacks(),
+            "`ReducedAttacks::from_template` failed to produce the attacks of a single Ship."
+        );
+        assert!(
+            ReducedAttacks::from_template(&template, 5) == ReducedAttacks::new(vec![
+                TargetedAttack::new(Attack::new(10, 10), 1),
+                TargetedAttack::new(Attack::new(5, 50), 3)
+            ]),
+            "`ReducedAttacks::from_template` failed to scale the attacks."
+        );
+        assert!(
+            ReducedAttacks::from_template(&template, 0).iter().all(|attack| attack.attack.parralel_attacks == 0),
+            "`ReducedAttacks::from_template` produced attacks from no Ships."
+        );
+    }
+    
+    #[test]
+    fn test_targeted_attack_checked() {
+        assert!(
+            TargetedAttack::checked(Attack::new(0, 10), 1)
+            .expect_err("`TargetedAttack::checked` failed to error on invalid `parralel_attacks`."
+            ) == AttacksError,
+            "`TargetedAttack::checked` returned incorrect `WeaponError`."
+        );
+        assert!(
+            TargetedAttack::checked(Attack::new(1, 0), 1)
+            .expect_err("`TargetedAttack::checked` failed to error on invalid `damage_per_attack`."
+            ) == DamageError,
+            "`TargetedAttack::checked` returned incorrect `WeaponError`."
+        );
+        assert!(
+            TargetedAttack::checked(Attack::new(1, 10), 1)
+            .expect("`TargetedAttack::checked` failed to create `TargetedAttack`."
+            ) == TargetedAttack::new(Attack::new(1, 10), 1),
+            "`TargetedAttack::checked` returned incorrect `TargetedAttack`."
+        );
+    }
+    
+    #[test]
+    fn test_is_spent() {
+        assert!(Attack::ZERO.is_spent(), "`Attack::is_spent` failed on `Attack::ZERO`.");
+        assert!(Attack::new(0, 10).is_spent(), "`Attack::is_spent` failed on a spent `Attack`.");
+        assert!(!Attack::new(1, 10).is_spent(), "`Attack::is_spent` registered an unspent `Attack`.");
+        assert!(Attack::ZERO == Attack::new(0, 0), "`Attack::ZERO` differs from an empty `Attack`.");
+    }
+    
+    #[test]
+    fn test_damage_against() {
+        let attack = TargetedAttack::new(Attack::new(3, 10), 2);
+        
+        assert!(attack.damage_against(1) == 0, "`TargetedAttack::damage_against` damaged an invalid target.");
+        assert!(attack.damage_against(2) == 30, "`TargetedAttack::damage_against` returned incorrect damage.");
+        assert!(attack.damage_against(5) == 30, "`TargetedAttack::damage_against` returned incorrect damage.");
+    }
+    
+    #[test]
+    fn test_reduced_attacks_len() {
+        let mut attacks = ReducedAttacks::new(vec![
+            TargetedAttack::new(Attack::new(2, 10), 1),
+            TargetedAttack::new(Attack::new(1, 50), 3)
+        ]);
+        assert!(attacks.len() == 2 && !attacks.is_empty(), "`ReducedAttacks::len` returned incorrect length.");
+        
+        attacks.iter_mut().for_each(|attack| attack.attack.parralel_attacks = 0);
+        attacks.clear_used_attacks();
+        assert!(attacks.len() == 0 && attacks.is_empty(), "`ReducedAttacks::is_empty` failed on cleared attacks.");
+    }
+    
+    #[test]
+    fn test_display() {
+        let attacks = ReducedAttacks::new(vec![
+            TargetedAttack::new(Attack::new(2, 10), 1),
+            TargetedAttack::new(Attack::new(3, 50), 4)
+        ]);
+        let rendered = attacks.to_string();
+        let rows = rendered.lines().skip(1).collect::<Vec<_>>();
+        
+        assert!(rows.len() == 2, "`ReducedAttacks` rendered an incorrect number of rows.");
+        assert!(
+            rows[0].split('|').map(str::trim).collect::<Vec<_>>() == vec!["1", "2", "10", "20"]
+            && rows[1].split('|').map(str::trim).collect::<Vec<_>>() == vec!["4", "3", "50", "150"],
+            "`ReducedAttacks` rendered incorrect rows."
+        );
+    }
+    
+    #[test]
+    fn test_from_template_saturates() {
+        let template = test_template()
+            .attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(70_000, 10), 1)]))
+            .build().expect("Failed to create template.");
+        
+        assert!(
+            ReducedAttacks::from_template(&template, 70_000).iter().next().unwrap().attack.parralel_attacks == UInt::MAX,
+            "`ReducedAttacks::from_template` failed to saturate."
+        );
+    }
+    
+    #[test]
+    fn test_with_crit() {
+        assert!(
+            Attack::new(1, 10).with_crit(2, 100).is_ok(),
+            "`Attack::with_crit` rejected a certain critical hit."
+        );
+        assert!(
+            Attack::new(1, 10).with_crit(2, 101) == Err(CritError),
+            "`Attack::with_crit` accepted a chance over 100."
+        );
+        assert!(
+            ::toml::from_str::<Attack>("parralel_attacks = 1\ndamage_per_attack = 10\ncrit_chance_percent = 101").is_err(),
+            "`Attack` deserialized a chance over 100."
+        );
     }
 }
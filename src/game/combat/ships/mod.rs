@@ -8,6 +8,7 @@
 use game::*;
 
 pub mod ship_error;
+pub mod weapon_error;
 pub mod attacks;
 pub mod ship_template;
 pub mod ship;
@@ -23,3 +24,47 @@ pub use self::reduced_ship::*;
 pub type ShipSize = UInt;
 /// A type alias for a unit of mass.
 pub type Mass = UInt;
+
+/// The smallest `ShipSize` of a capital Ship.
+pub const CAPITAL_SIZE: ShipSize = 10;
+
+/// Returns the name of the class of Ship with the passed `ShipSize`.
+///
+/// #Params
+///
+/// size --- The `ShipSize` to name.
+pub fn size_class_name(size: ShipSize) -> &'static str {
+    match size {
+        0..=2 => "Fighter",
+        3..=5 => "Corvette",
+        6..=9 => "Cruiser",
+        _ => "Capital"
+    }
+}
+
+/// Returns true if a Ship with the passed `ShipSize` is a capital Ship.
+///
+/// #Params
+///
+/// size --- The `ShipSize` to check.
+pub fn is_capital(size: ShipSize) -> bool {
+    size >= CAPITAL_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_size_class_name() {
+        assert!(size_class_name(2) == "Fighter", "`size_class_name` failed on the largest Fighter.");
+        assert!(size_class_name(3) == "Corvette", "`size_class_name` failed on the smallest Corvette.");
+        assert!(size_class_name(5) == "Corvette", "`size_class_name` failed on the largest Corvette.");
+        assert!(size_class_name(6) == "Cruiser", "`size_class_name` failed on the smallest Cruiser.");
+        assert!(size_class_name(9) == "Cruiser", "`size_class_name` failed on the largest Cruiser.");
+        assert!(size_class_name(CAPITAL_SIZE) == "Capital", "`size_class_name` failed on the smallest Capital.");
+        
+        assert!(!is_capital(CAPITAL_SIZE - 1), "`is_capital` accepted a Cruiser.");
+        assert!(is_capital(CAPITAL_SIZE), "`is_capital` rejected a Capital.");
+    }
+}
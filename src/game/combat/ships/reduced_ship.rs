@@ -7,13 +7,16 @@
 //! Date: 2017/11/10
 
 use game::*;
-use super::ship_template::{HullPoint, ShieldPoint};
+use super::ship_template::{ShipTemplate, HullPoint, ShieldPoint};
 use super::attacks::*;
 use super::ship::*;
+use super::weapon_error::*;
+use super::ship_error::*;
+use super::Mass;
 
 /// A `ReducedShip` represents multiple instances of a `ShipTemplate` simulated using a
 /// shared average state.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct ReducedShip {
     /// The `Ship` instance which represents the average state of all the `Ship`s in this
     /// `ReducedShip`.
@@ -35,21 +38,134 @@ impl ReducedShip {
             number
         }
     }
+    /// Attempts to call `ReducedShip::new` if every attack of the template of
+    /// `average_ship` passes the checks of `TargetedAttack::checked`.
+    ///
+    /// #Params
+    ///
+    /// Refer to `ReducedShip::new` for parameters.
+    ///
+    /// #Errors
+    ///
+    /// Refer to `TargetedAttack::checked` for errors.
+    pub fn try_new(average_ship: Ship, number: UInt) -> Result<Self, WeaponError> {
+        //Check every attack of the template.
+        for attack in average_ship.get_attacks().iter() {
+            TargetedAttack::checked(attack.attack, attack.smallest_target)?;
+        }
+        
+        Ok(Self::new(average_ship, number))
+    }
     /// Returns true if the `ReducedShip` still has `Ship`s in the group.
     pub fn is_alive(&self) -> bool {
         self.number != 0
     }
+    /// Returns the fraction of the original `Ship`s in this group which are still alive,
+    /// in the range [0, 1].
+    ///
+    /// #Params
+    ///
+    /// original_count --- The number of `Ship`s originally in this group.
+    pub fn fraction_alive(&self, original_count: UInt) -> f32 {
+        if original_count == 0 {
+            0.0
+        } else {
+            (self.number as f32 / original_count as f32).max(0.0).min(1.0)
+        }
+    }
+    /// Returns the number of the original `Ship`s in this group which have been destroyed.
+    ///
+    /// #Params
+    ///
+    /// original_count --- The number of `Ship`s originally in this group.
+    pub fn casualties(&self, original_count: UInt) -> UInt {
+        original_count.saturating_sub(self.number)
+    }
+    /// Returns the units of Mass carried by all the `Ship`s in this group.
+    pub fn total_cargo(&self) -> Mass {
+        self.average_ship.get_cargo().saturating_mul(self.number)
+    }
+    /// Attempts to spread `total` units of Mass evenly across the `Ship`s in this group.
+    ///
+    /// #Params
+    ///
+    /// total --- The units of Mass carried by the whole group.
+    ///
+    /// #Errors
+    ///
+    /// CargoError --- total > cargo_capacity * number || total % number != 0
+    pub fn load_cargo_group(&mut self, total: Mass) -> Result<(), ShipError> {
+        //Check that the cargo fits in the whole group.
+        if total as u64 > self.average_ship.get_cargo_capacity() as u64 * self.number as u64 {
+            Err(CargoError)
+        //An empty group can only fit no cargo.
+        } else if self.number == 0 {
+            self.average_ship.set_cargo(0)
+        //Check that the cargo divides evenly between the `Ship`s.
+        } else if total % self.number != 0 {
+            Err(CargoError)
+        } else {
+            self.average_ship.set_cargo(total / self.number)
+        }
+    }
     /// Regenerates shields for this `ReducedShip`.
     pub fn regenerate_shields(&mut self) {
         self.average_ship.regenerate_shields()
     }
+    /// Restores up to `per_ship` hull points to each `Ship` in this group.
+    ///
+    /// #Params
+    ///
+    /// per_ship --- The hull points to restore to each `Ship`.
+    pub fn repair_group(&mut self, per_ship: HullPoint) {
+        self.average_ship.repair_hull(per_ship)
+    }
+    /// Adds `ships` `Ship`s back into this group with `at_hull` hull points each,
+    /// adjusting the average hull.
+    /// `at_hull` is capped at the max hull of the template and nothing is added if it
+    /// is 0, as a `Ship` with no hull is destroyed.
+    /// The added `Ship`s take on the average shields and fuel of the group.
+    ///
+    /// #Params
+    ///
+    /// ships --- The number of `Ship`s to add.
+    /// at_hull --- The hull points of each added `Ship`.
+    pub fn revive(&mut self, ships: UInt, at_hull: HullPoint) {
+        let ships = ships.min(UInt::MAX - self.number);
+        
+        if ships == 0 || at_hull == 0 {
+            return;
+        }
+        
+        let at_hull = at_hull.min(self.average_ship.get_max_hull());
+        //The total hull of the group after the `Ship`s are added.
+        let total_hull = self.average_ship.get_hull_points() as u64 * self.number as u64
+            + at_hull as u64 * ships as u64;
+        
+        self.number += ships;
+        self.average_ship.set_hull_points((total_hull / self.number as u64) as HullPoint).ok();
+    }
+    /// Uses one period of fuel for this `ReducedShip`.
+    pub fn use_fuel(&mut self) {
+        self.average_ship.use_fuel()
+    }
     /// Resolves damage dealt against this group of `Ship`s and returns any which was not
     /// used to destroy the `Ship`s.
     ///
     /// #Params
     ///
     /// damage --- The damage leveled against this `ReducedShip`.
-    pub fn resolve_damage(&mut self, mut damage: DamagePoint) -> DamagePoint {
+    pub fn resolve_damage(&mut self, damage: DamagePoint) -> DamagePoint {
+        self.resolve_damage_with(damage, false)
+    }
+    /// Resolves damage dealt against this group of `Ship`s, which may ignore shields,
+    /// and returns any which was not used to destroy the `Ship`s.
+    ///
+    /// #Params
+    ///
+    /// damage --- The damage leveled against this `ReducedShip`.
+    /// ignore_shields --- Whether the damage ignores shields and damages hull directly.
+    pub fn resolve_damage_with(&mut self, mut damage: DamagePoint, ignore_shields: bool) -> DamagePoint {
         //The total amount of remaining hull points of all the ships.
         let mut remaining_hull = 0u64;
         //The total amount of remaining shield points of all the ships.
@@ -69,7 +185,7 @@ impl ReducedShip {
             damage -= portion;
             
             //Simulate the portion being used against this ship.
-            let simulation = self.average_ship.simulate_damage(portion);
+            let simulation = self.average_ship.simulate_damage_with(portion, ignore_shields);
             
             //Check whether the ship died (its hull is 0).
             if simulation.0 == 0 {
@@ -93,7 +209,8 @@ impl ReducedShip {
             to_iterate = ::std::cmp::min(to_iterate - 1, damage);
         }
         
-        //Check whether there's any ships left alive.
+        //Check whether there's any ships left alive, this also guards the divisions by
+        //`self.number` below.
         if self.is_alive() {
             //Add to the remaining hull the hull of all ships which were not attacked.
             remaining_hull += self.average_ship.get_hull_points() as u64 * unattacked as u64;
@@ -116,8 +233,20 @@ impl ReducedShip {
     ///
     /// attacks --- The attacks leveled against this `ReducedShip`.
     pub fn resolve_attacks(&mut self, attacks: &mut ReducedAttacks) {
+        self.resolve_attacks_with(attacks, &mut NoCrits)
+    }
+    /// Resolves attacks leveled against this group of `Ship`s, rolling for critical hits
+    /// once for each group of attacks, and returns any which were not used to destroy
+    /// the `Ship`s.
+    /// This function does not clear away used attacks in `attacks`
+    ///
+    /// #Params
+    ///
+    /// attacks --- The attacks leveled against this `ReducedShip`.
+    /// rng --- The `CombatRng` used to roll for critical hits.
+    pub fn resolve_attacks_with<R: CombatRng>(&mut self, attacks: &mut ReducedAttacks, rng: &mut R) {
         //The size class of this `ReducedShip`.
-        let size_class = (*self.as_ref()).ship_size_class;
+        let size_class = (*self.as_ref()).get_ship_size_class();
         //The iterator over each group of targeted attacks, filtered by those which can
         //target the ships in this `ReducedShip`.
         let mut iter = attacks.iter_mut()
@@ -130,23 +259,75 @@ impl ReducedShip {
                 //If there's still attacks left then resolve their damage against this
                 //`ReducedShip`.
                 Some(attack) => {
+                    //The damage of each attack in this group, depending on whether it
+                    //lands a critical hit.
+                    let damage_per_attack = if rng.roll_percent() < attack.attack.crit_chance_percent {
+                        attack.attack.crit_damage()
+                    } else {
+                        attack.attack.damage_per_attack
+                    };
+                    
+                    //Attacks which deal no damage are spent without effect.
+                    if damage_per_attack == 0 {
+                        attack.attack.parralel_attacks = 0;
                     //If there is still unused damage then `parralel_attacks` is set
                     //accordingly, else it's zeroed.
-                    attack.attack.parralel_attacks =
-                        self.resolve_damage(attack.attack.sum_damage())
-                        / attack.attack.damage_per_attack;
+                    } else {
+                        attack.attack.parralel_attacks = self.resolve_damage_with(
+                            attack.attack.parralel_attacks.saturating_mul(damage_per_attack),
+                            attack.attack.bypass_shields
+                        ) / damage_per_attack;
+                    }
                 },
                 //If there's no more attacks left then their all resolved.
                 None => break
             }
         }
     }
+    /// Predicts the outcome of resolving `attacks` against this `ReducedShip` without
+    /// modifying it or `attacks`.
+    /// Returns the number of surviving `Ship`s and their average hull and shield points.
+    ///
+    /// #Params
+    ///
+    /// attacks --- The attacks to preview against this `ReducedShip`.
+    pub fn preview_resolve(&self, attacks: &ReducedAttacks) -> (UInt, HullPoint, ShieldPoint) {
+        //Resolve the attacks against copies so that the originals are untouched.
+        let mut preview = self.clone();
+        preview.resolve_attacks(&mut attacks.clone());
+        
+        (preview.number, preview.average_ship.get_hull_points(), preview.average_ship.get_shield_points())
+    }
     /// Calculates the attacks produced by all of the ships in this `ReducedShip` in
     /// parralel.
     pub fn get_attacks(&self) -> ReducedAttacks {
-        let mut attacks = self.average_ship.attacks.clone();
-        attacks.iter_mut().for_each(|attack| attack.attack.parralel_attacks *= self.number);
-        attacks
+        ReducedAttacks::from_template(&self.average_ship, self.number)
+    }
+    /// Calculates the attacks produced by the `Ship`s still alive in this `ReducedShip`.
+    pub fn current_attacks(&self) -> ReducedAttacks {
+        self.get_attacks()
+    }
+    /// Calculates the attacks produced by a full group of `Ship`s of a template, before
+    /// any casualties.
+    ///
+    /// #Params
+    ///
+    /// template --- The template of the `Ship`s in the group.
+    /// full_count --- The number of `Ship`s in the full group.
+    pub fn max_attacks(template: &ShipTemplate, full_count: UInt) -> ReducedAttacks {
+        ReducedAttacks::from_template(template, full_count)
+    }
+}
+
+impl factions::AllignedInstance<Ship> {
+    /// Converts this `Ship` into a `ReducedShip` of `number` `Ship`s alligned with the
+    /// same `Faction`.
+    ///
+    /// #Params
+    ///
+    /// number --- The number of `Ship`s in the group.
+    pub fn into_reduced(self, number: UInt) -> factions::AllignedInstance<ReducedShip> {
+        self.map(|ship| ReducedShip::new(ship, number))
     }
 }
 
@@ -155,3 +336,287 @@ impl AsRef<Ship> for ReducedShip {
         &self.average_ship
     }
 }
+
+/// Resolves a single exchange between two `ReducedShip`s: the attacker fires first and
+/// the surviving defenders fire back.
+///
+/// #Params
+///
+/// attacker --- The `ReducedShip` which fires first.
+/// defender --- The `ReducedShip` which returns fire with its survivors.
+pub fn engage(attacker: &mut ReducedShip, defender: &mut ReducedShip) {
+    defender.resolve_attacks(&mut attacker.current_attacks());
+    attacker.resolve_attacks(&mut defender.current_attacks());
+}
+
+/// Returns the number of periods a fleet of `ReducedShip`s can run before its shortest
+/// ranged group runs out of fuel, or `UInt::MAX` if no group uses fuel.
+/// Groups with no `Ship`s left are ignored.
+///
+/// #Params
+///
+/// ships --- The groups of `Ship`s in the fleet.
+pub fn fleet_periods_of_fuel(ships: &[ReducedShip]) -> UInt {
+    ships.iter()
+    .filter(|group| group.is_alive())
+    .map(|group| group.as_ref().periods_of_fuel())
+    .min()
+    .unwrap_or(UInt::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ship_template::*;
+    
+    #[test]
+    fn test_preview_resolve() {
+        let template = shared(test_template());
+        let group = ReducedShip::new(Ship::from(template), 5);
+        let attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(10, 70), 1)]);
+        
+        let preview = group.preview_resolve(&attacks);
+        assert!(preview == (5, 60, 0), "`ReducedShip::preview_resolve` returned incorrect prediction.");
+        assert!(group.number == 5, "`ReducedShip::preview_resolve` modified the number of `Ship`s.");
+        assert!(
+            group.as_ref().get_hull_points() == 100
+            && group.as_ref().get_shield_points() == 100,
+            "`ReducedShip::preview_resolve` modified the average `Ship`."
+        );
+        assert!(
+            attacks == ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(10, 70), 1)]),
+            "`ReducedShip::preview_resolve` modified the attacks."
+        );
+    }
+    
+    #[test]
+    fn test_resolve_zero_damage_attacks() {
+        let template = shared(test_template());
+        let mut group = ReducedShip::new(Ship::from(template), 5);
+        let mut attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(10, 0), 1)]);
+        
+        group.resolve_attacks(&mut attacks);
+        assert!(
+            attacks.iter().all(|attack| attack.attack.parralel_attacks == 0),
+            "`ReducedShip::resolve_attacks` failed to spend zero damage attacks."
+        );
+        assert!(group.number == 5, "`ReducedShip::resolve_attacks` destroyed `Ship`s with zero damage attacks.");
+    }
+    
+    #[test]
+    fn test_into_reduced() {
+        let template = shared(test_template());
+        let ship = factions::AllignedInstance(3, Ship::from(template.clone()));
+        
+        let group = ship.into_reduced(5);
+        assert!(
+            group == factions::AllignedInstance(3, ReducedShip::new(Ship::from(template), 5)),
+            "`AllignedInstance::into_reduced` returned incorrect `ReducedShip`."
+        );
+    }
+    
+    #[test]
+    fn test_fraction_alive() {
+        let template = shared(test_template());
+        let group = ReducedShip::new(Ship::from(template), 3);
+        
+        assert!(group.fraction_alive(4) == 0.75, "`ReducedShip::fraction_alive` returned incorrect fraction.");
+        assert!(group.fraction_alive(2) == 1.0, "`ReducedShip::fraction_alive` exceeded 1.");
+        assert!(group.fraction_alive(0) == 0.0, "`ReducedShip::fraction_alive` failed on no original `Ship`s.");
+        
+        assert!(group.casualties(4) == 1, "`ReducedShip::casualties` returned incorrect casualties.");
+        assert!(group.casualties(2) == 0, "`ReducedShip::casualties` underflowed.");
+        assert!(group.casualties(0) == 0, "`ReducedShip::casualties` failed on no original `Ship`s.");
+    }
+    
+    #[test]
+    fn test_current_attacks() {
+        let template = shared(test_template().attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(2, 60), 1)])));
+        let mut group = ReducedShip::new(Ship::from(template.clone()), 5);
+        let max = ReducedShip::max_attacks(&template, 5);
+        
+        assert!(group.current_attacks() == max, "`ReducedShip::current_attacks` did not match the full group.");
+        
+        //Two `Ship`s are lost.
+        group.number = 3;
+        assert!(
+            group.current_attacks() == ReducedShip::max_attacks(&template, 3),
+            "`ReducedShip::current_attacks` did not reflect casualties."
+        );
+        assert!(group.current_attacks() != max, "`ReducedShip::max_attacks` changed with casualties.");
+    }
+    
+    #[test]
+    fn test_try_new() {
+        let template = shared(test_template().attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(2, 60), 1)])));
+        assert!(
+            ReducedShip::try_new(Ship::from(template.clone()), 3) == Ok(ReducedShip::new(Ship::from(template), 3)),
+            "`ReducedShip::try_new` rejected valid attacks."
+        );
+        
+        let template = shared(test_template().attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(2, 0), 1)])));
+        assert!(
+            ReducedShip::try_new(Ship::from(template), 3) == Err(DamageError),
+            "`ReducedShip::try_new` accepted an attack without damage."
+        );
+        
+        let template = shared(test_template().attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(0, 60), 1)])));
+        assert!(
+            ReducedShip::try_new(Ship::from(template), 3) == Err(AttacksError),
+            "`ReducedShip::try_new` accepted an attack without attacks."
+        );
+    }
+    
+    #[test]
+    fn test_engage() {
+        let template = |hull, attack| shared(
+            test_template()
+            .max_hull(hull)
+            .shield_capacity(10)
+            .attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, attack), 1)]))
+        );
+        
+        let mut strong = ReducedShip::new(Ship::from(template(100, 100)), 10);
+        let mut weak = ReducedShip::new(Ship::from(template(100, 100)), 2);
+        engage(&mut strong, &mut weak);
+        assert!(!weak.is_alive(), "`engage` failed to destroy the weaker group.");
+        assert!(
+            strong.number == 10 && strong.as_ref().get_hull_points() == 100,
+            "`engage` let a destroyed group return fire."
+        );
+        
+        let mut first = ReducedShip::new(Ship::from(template(100, 60)), 2);
+        let mut second = ReducedShip::new(Ship::from(template(100, 60)), 2);
+        engage(&mut first, &mut second);
+        assert!(
+            first.is_alive() && first.as_ref().get_hull_points() == 50
+            && second.is_alive() && second.as_ref().get_hull_points() == 50,
+            "`engage` failed to resolve an even exchange."
+        );
+    }
+    
+    #[test]
+    fn test_load_cargo_group() {
+        let template = shared(test_template().cargo_capacity(20));
+        let mut group = ReducedShip::new(Ship::from(template), 4);
+        
+        assert!(group.load_cargo_group(60) == Ok(()), "`ReducedShip::load_cargo_group` refused cargo within capacity.");
+        assert!(
+            group.as_ref().get_cargo() == 15 && group.total_cargo() == 60,
+            "`ReducedShip::load_cargo_group` failed to spread the cargo evenly."
+        );
+        assert!(group.load_cargo_group(81) == Err(CargoError), "`ReducedShip::load_cargo_group` exceeded the group capacity.");
+        assert!(group.total_cargo() == 60, "`ReducedShip::load_cargo_group` modified cargo on error.");
+        
+        assert!(group.load_cargo_group(7) == Err(CargoError), "`ReducedShip::load_cargo_group` accepted uneven cargo.");
+        assert!(group.total_cargo() == 60, "`ReducedShip::load_cargo_group` loaded the uneven cargo.");
+    }
+    
+    #[test]
+    fn test_resolve_damage_kills_all() {
+        let template = shared(test_template().shield_capacity(10));
+        let mut group = ReducedShip::new(Ship::from(template), 1000);
+        
+        //Each `Ship` absorbs 110 damage, the rest is returned.
+        assert!(group.resolve_damage(200_000) == 90_000, "`ReducedShip::resolve_damage` returned incorrect unused damage.");
+        assert!(!group.is_alive(), "`ReducedShip::resolve_damage` failed to destroy every `Ship`.");
+        assert!(group.resolve_damage(500) == 500, "`ReducedShip::resolve_damage` used damage against a destroyed group.");
+    }
+    
+    #[test]
+    fn test_resolve_damage_overkill_reduction() {
+        let template = shared(test_template().shield_capacity(0).shield_recovery(0).damage_reduction(50));
+        let mut group = ReducedShip::new(Ship::from(template), 2);
+        
+        //Each `Ship` absorbs 200 incoming damage, the rest is returned unreduced.
+        assert!(group.resolve_damage(600) == 200, "`ReducedShip::resolve_damage` reduced the overkill damage twice.");
+        assert!(!group.is_alive(), "`ReducedShip::resolve_damage` left `Ship`s alive.");
+    }
+    
+    #[test]
+    fn test_resolve_saturated_volley() {
+        let attacker = shared(
+            test_template()
+            .attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 10), 1)]))
+        );
+        let attacker = ReducedShip::new(Ship::from(attacker), UInt::MAX);
+        let mut defender = ReducedShip::new(Ship::from(shared(test_template())), 5);
+        
+        let mut attacks = attacker.get_attacks();
+        defender.resolve_attacks(&mut attacks);
+        assert!(!defender.is_alive(), "`ReducedShip::resolve_attacks` failed to resolve a saturated volley.");
+    }
+    
+    #[test]
+    fn test_resolve_bypass_shields() {
+        let template = shared(test_template().shield_capacity(50));
+        let mut group = ReducedShip::new(Ship::from(template), 4);
+        let mut attacks = ReducedAttacks::new(vec![
+            TargetedAttack::new(Attack::new(4, 30).with_bypass_shields(true), 1)
+        ]);
+        
+        group.resolve_attacks(&mut attacks);
+        assert!(group.number == 4, "`ReducedShip::resolve_attacks` killed `Ship`s with too little damage.");
+        assert!(
+            group.as_ref().get_hull_points() == 70
+            && group.as_ref().get_shield_points() == 50,
+            "`ReducedShip::resolve_attacks` ignored `bypass_shields`."
+        );
+    }
+    
+    #[test]
+    fn test_resolve_crit_attacks() {
+        let template = shared(test_template().shield_capacity(0).shield_recovery(0));
+        let attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(4, 10).with_crit(3, 50).expect("Failed to create attack."), 1)]);
+        
+        let mut group = ReducedShip::new(Ship::from(template.clone()), 2);
+        group.resolve_attacks_with(&mut attacks.clone(), &mut ForcedRng(10));
+        assert!(group.as_ref().get_hull_points() == 40, "`ReducedShip::resolve_attacks_with` failed to land a critical hit.");
+        
+        let mut group = ReducedShip::new(Ship::from(template), 2);
+        group.resolve_attacks_with(&mut attacks.clone(), &mut ForcedRng(50));
+        assert!(group.as_ref().get_hull_points() == 80, "`ReducedShip::resolve_attacks_with` landed a missed critical hit.");
+    }
+    
+    #[test]
+    fn test_fleet_periods_of_fuel() {
+        let long = ReducedShip::new(Ship::from(shared(test_template())), 3);
+        let short = ReducedShip::new(Ship::from(shared(test_template().fuel_use(4))), 2);
+        let idle = ReducedShip::new(Ship::from(shared(test_template().fuel_use(0))), 1);
+        let mut dead = ReducedShip::new(Ship::from(shared(test_template().fuel_use(10))), 1);
+        dead.number = 0;
+        
+        assert!(
+            fleet_periods_of_fuel(&[long.clone(), short, idle.clone(), dead]) == 2,
+            "`fleet_periods_of_fuel` failed to find the shortest range."
+        );
+        assert!(fleet_periods_of_fuel(&[idle]) == UInt::MAX, "`fleet_periods_of_fuel` failed on a fleet which uses no fuel.");
+        assert!(fleet_periods_of_fuel(&[]) == UInt::MAX, "`fleet_periods_of_fuel` failed on an empty fleet.");
+        assert!(fleet_periods_of_fuel(&[long]) == 10, "`fleet_periods_of_fuel` failed on a single group.");
+    }
+    
+    #[test]
+    fn test_repair_and_revive() {
+        let template = shared(test_template());
+        let mut group = ReducedShip::new(Ship::new(template, 10, 40, 100).expect("Failed to create `Ship`."), 3);
+        
+        group.repair_group(20);
+        assert!(group.as_ref().get_hull_points() == 60, "`ReducedShip::repair_group` restored incorrect hull.");
+        
+        group.revive(1, 100);
+        assert!(
+            group.number == 4 && group.as_ref().get_hull_points() == 70,
+            "`ReducedShip::revive` failed to adjust the average hull."
+        );
+        group.revive(2, 1000);
+        assert!(
+            group.number == 6 && group.as_ref().get_hull_points() == 80,
+            "`ReducedShip::revive` exceeded the max hull."
+        );
+        group.revive(2, 0);
+        assert!(
+            group.number == 6 && group.as_ref().get_hull_points() == 80,
+            "`ReducedShip::revive` added destroyed `Ship`s."
+        );
+    }
+}
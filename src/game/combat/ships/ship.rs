@@ -6,11 +6,28 @@
 //! Date: 2017/11/10
 
 use game::*;
+use super::Mass;
 use super::ship_error::*;
 use super::ship_template::*;
 use super::attacks::*;
 use std::rc::Rc;
 use std::ops::Deref;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::{ser, de};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// `ShipStatus` is a summary of the condition of a `Ship`.
+pub enum ShipStatus {
+    /// The `Ship` has no hull left.
+    Destroyed,
+    /// The `Ship` has less than a quarter of its hull left.
+    Critical,
+    /// The `Ship` has lost hull or shields.
+    Damaged,
+    /// The `Ship` has full hull and shields.
+    Pristine
+}
+pub use self::ShipStatus::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// `ShipTemplate` is a representation of a type of Ship.
@@ -22,11 +39,13 @@ pub struct Ship {
     /// The current hull points (health points) of this Ship type.
     hull_points: HullPoint,
     /// The current shield points of this Ship type.
-    shield_points: ShieldPoint
+    shield_points: ShieldPoint,
+    /// The units of Mass currently carried by this Ship.
+    cargo: Mass
 }
 
 impl Ship {
-    /// Returns a new `Ship` built from raw parts.
+    /// Returns a new `Ship` built from raw parts, carrying no cargo.
     ///
     /// #Params
     ///
@@ -39,7 +58,8 @@ impl Ship {
             template,
             fuel_units,
             hull_points,
-            shield_points
+            shield_points,
+            cargo: 0
         }
     }
     /// Attempts to call `Ship::from_parts` if parameters pass checks.
@@ -57,7 +77,7 @@ impl Ship {
         if fuel_units > template.get_fuel_capacity() {
             Err(FuelError)
         //Check that hull is not greater than max hull.
-        } else if hull_points > template.max_hull {
+        } else if hull_points > template.get_max_hull() {
             Err(HullError)
         //Check that shield is not greater than shield capacity.
         } else if shield_points > template.get_shield_capacity() {
@@ -88,25 +108,43 @@ impl Ship {
     /// FuelError --- self.fuel_units > val.max_hull
     /// HullError --- self.hull > val.max_hull
     /// HullError --- self.shield_points > val.shield_capacity
+    /// CargoError --- self.cargo > val.cargo_capacity
     pub fn set_template(&mut self, val: Rc<ShipTemplate>) -> Result<(), ShipError> {
         //Check that fuel is not greater than fuel capacity.
         if self.fuel_units > val.get_fuel_capacity() {
             Err(FuelError)
         //Check that hull is not greater than max hull.
-        } else if self.hull_points > val.max_hull {
+        } else if self.hull_points > val.get_max_hull() {
             Err(HullError)
         //Check that shield is not greater than shield capacity.
         } else if self.shield_points > val.get_shield_capacity() {
             Err(ShieldError)
+        //Check that cargo is not greater than cargo capacity.
+        } else if self.cargo > val.get_cargo_capacity() {
+            Err(CargoError)
         //All checks passed, template is valid.
         } else {
             self.template = val; Ok(())
         }
     }
+    /// Returns the name of the `ShipTemplate` of this `Ship` if it is loaded in the game
+    /// templates.
+    pub fn template_name(&self) -> Option<String> {
+        get_game_templates().name_of(&self.template).cloned()
+    }
     /// Returns the `fuel_units` of this `Ship`.
     pub fn get_fuel_units(&self) -> FuelUnit {
         self.fuel_units
     }
+    /// Returns the number of periods this `Ship` can run on its remaining fuel, or
+    /// `UInt::MAX` if it uses no fuel.
+    pub fn periods_of_fuel(&self) -> UInt {
+        self.fuel_units.checked_div(self.template.get_fuel_use()).unwrap_or(UInt::MAX)
+    }
+    /// Uses one period of fuel, stopping at empty.
+    pub fn use_fuel(&mut self) {
+        self.fuel_units = self.fuel_units.saturating_sub(self.template.get_fuel_use())
+    }
     /// Attempts to set the `fuel_units` of this `Ship`.
     ///
     /// #Params
@@ -137,12 +175,24 @@ impl Ship {
     ///
     /// HullError --- val > template.max_hull
     pub fn set_hull_points(&mut self, val: HullPoint) -> Result<(), ShipError> {
-        if val > self.template.max_hull {
+        if val > self.template.get_max_hull() {
             Err(HullError)
         } else {
             self.hull_points = val; Ok(())
         }
     }
+    /// Restores up to `amount` hull points to this `Ship`, stopping at the max hull of its
+    /// template.
+    ///
+    /// #Params
+    ///
+    /// amount --- The hull points to restore.
+    pub fn repair_hull(&mut self, amount: HullPoint) {
+        self.hull_points = ::std::cmp::min(
+            self.hull_points.saturating_add(amount),
+            self.template.get_max_hull()
+        )
+    }
     /// Returns the `shield_points` of this `Ship`.
     pub fn get_shield_points(&self) -> ShieldPoint {
         self.shield_points
@@ -163,10 +213,68 @@ impl Ship {
             self.shield_points = val; Ok(())
         }
     }
+    /// Returns the units of Mass currently carried by this `Ship`.
+    pub fn get_cargo(&self) -> Mass {
+        self.cargo
+    }
+    /// Attempts to add `mass` units of Mass to the cargo carried by this `Ship`.
+    ///
+    /// #Params
+    ///
+    /// mass --- The units of Mass to load.
+    ///
+    /// #Errors
+    ///
+    /// CargoError --- cargo + mass > template.cargo_capacity
+    pub fn load_cargo(&mut self, mass: Mass) -> Result<(), ShipError> {
+        match self.cargo.checked_add(mass) {
+            Some(cargo) => self.set_cargo(cargo),
+            None => Err(CargoError)
+        }
+    }
+    /// Attempts to set the units of Mass carried by this `Ship`.
+    ///
+    /// #Params
+    ///
+    /// val --- The new value to set.
+    ///
+    /// #Errors
+    ///
+    /// CargoError --- val > template.cargo_capacity
+    pub fn set_cargo(&mut self, val: Mass) -> Result<(), ShipError> {
+        if val > self.template.get_cargo_capacity() {
+            Err(CargoError)
+        } else {
+            self.cargo = val; Ok(())
+        }
+    }
+    /// Returns true if `other` is the same type of Ship as this `Ship`, regardless of
+    /// the current state of either.
+    ///
+    /// #Params
+    ///
+    /// other --- The other `Ship` to compare against.
+    pub fn same_type(&self, other: &Ship) -> bool {
+        Rc::ptr_eq(&self.template, &other.template) || self.template == other.template
+    }
     /// Returns true if this `Ship` is alive.
     pub fn is_alive(&self) -> bool {
         self.hull_points != 0
     }
+    /// Returns the `ShipStatus` of this `Ship`.
+    pub fn status(&self) -> ShipStatus {
+        if !self.is_alive() {
+            Destroyed
+        //Check whether there is less than a quarter of the hull left.
+        } else if (self.hull_points as u64) * 4 < self.template.get_max_hull() as u64 {
+            Critical
+        } else if self.hull_points < self.template.get_max_hull()
+            || self.shield_points < self.template.get_shield_capacity() {
+            Damaged
+        } else {
+            Pristine
+        }
+    }
     /// Regenerates shields for this `Ship`, capping the shields off at the shield
     /// capacity of `self.template`.
     pub fn regenerate_shields(&mut self) {
@@ -182,23 +290,53 @@ impl Ship {
     /// #Params
     ///
     /// damage --- The damage leveled against this `Ship`.
-    pub fn simulate_damage(&mut self, mut damage: DamagePoint) -> (HullPoint, ShieldPoint, DamagePoint) {
+    pub fn simulate_damage(&mut self, damage: DamagePoint) -> (HullPoint, ShieldPoint, DamagePoint) {
+        self.simulate_damage_with(damage, false)
+    }
+    /// Simulates damage dealt against this `Ship`, which may ignore shields, and returns
+    /// any which would not used to destroy this `Ship`.
+    ///
+    /// #Params
+    ///
+    /// damage --- The damage leveled against this `Ship`.
+    /// ignore_shields --- Whether the damage ignores shields and damages hull directly.
+    pub fn simulate_damage_with(&self, damage: DamagePoint, ignore_shields: bool) -> (HullPoint, ShieldPoint, DamagePoint) {
+        //The damage reduction of the template, capped at 100%.
+        let reduction = ::std::cmp::min(self.template.get_damage_reduction(), 100) as u64;
+        //Some of the damage is absorbed before it reaches the shields.
+        let mut reduced = damage - (damage as u64 * reduction / 100) as DamagePoint;
+        
+        //The shields which can soak up the damage.
+        let shield_points = if ignore_shields { 0 } else { self.shield_points };
+        //The shields which are left untouched by the damage.
+        let untouched = self.shield_points - shield_points;
+        
         //If there's enough shields to take the damage then there will be no damage to
         //hull and no damage left...
-        if damage < self.shield_points {
-            (self.hull_points, self.shield_points - damage, 0)
+        if reduced < shield_points {
+            (self.hull_points, shield_points - reduced, 0)
         //Else there will be no more shields and there may be hull damage.
         } else {
             //The shields soak up some damage.
-            damage -= self.shield_points;
+            reduced -= shield_points;
             
             //If there's enough hull to survive the damage then there will be no damage
             //left over...
-            if damage < self.hull_points {
-                (self.hull_points - damage, 0, 0)
+            if reduced < self.hull_points {
+                (self.hull_points - reduced, untouched, 0)
             //Else there will be no hull and maybe some damage left over.
             } else {
-                (0, 0, damage - self.hull_points)
+                //The reduced damage used to destroy this `Ship`.
+                let absorbed = shield_points as u64 + self.hull_points as u64;
+                //The incoming damage used to destroy this `Ship`, the leftover damage is
+                //returned unreduced so that it can be reduced by the next target.
+                let used = if reduction == 100 {
+                    damage as u64
+                } else {
+                    (absorbed * 100).div_ceil(100 - reduction)
+                };
+                
+                (0, untouched, (damage as u64).saturating_sub(used) as DamagePoint)
             }
         }
     }
@@ -209,8 +347,18 @@ impl Ship {
     ///
     /// damage --- The damage leveled against this `Ship`.
     pub fn resolve_damage(&mut self, damage: DamagePoint) -> DamagePoint {
+        self.resolve_damage_with(damage, false)
+    }
+    /// Resolves damage dealt against this `Ship`, which may ignore shields, and returns
+    /// any which was not used to destroy this `Ship`.
+    ///
+    /// #Params
+    ///
+    /// damage --- The damage leveled against this `Ship`.
+    /// ignore_shields --- Whether the damage ignores shields and damages hull directly.
+    pub fn resolve_damage_with(&mut self, damage: DamagePoint, ignore_shields: bool) -> DamagePoint {
         //Simulate the damage.
-        let simulation = self.simulate_damage(damage);
+        let simulation = self.simulate_damage_with(damage, ignore_shields);
         
         //Apply the simulation to the hull.
         self.hull_points = simulation.0;
@@ -226,8 +374,18 @@ impl Ship {
     ///
     /// attacks --- The attacks leveled against this `Ship`.
     pub fn resolve_attacks(&mut self, attacks: &mut ReducedAttacks) {
+        self.resolve_attacks_with(attacks, &mut NoCrits)
+    }
+    /// Resolves attacks leveled against this `Ship`, rolling for critical hits, and
+    /// returns any which was not used to destroy this `Ship`.
+    ///
+    /// #Params
+    ///
+    /// attacks --- The attacks leveled against this `Ship`.
+    /// rng --- The `CombatRng` used to roll for critical hits.
+    pub fn resolve_attacks_with<R: CombatRng>(&mut self, attacks: &mut ReducedAttacks, rng: &mut R) {
         //The size class of this `Ship`.
-        let size_class = self.template.as_ref().ship_size_class;
+        let size_class = self.template.as_ref().get_ship_size_class();
         //An iterator over all the attacks, filtered by those which can target this `Ship`.
         let mut iter = attacks.iter_mut()
         .filter(|attack| attack.valid_target(size_class));
@@ -237,11 +395,26 @@ impl Ship {
             match iter.next() {
                 //If there's attacks left...
                 Some(attack) => {
+                    //The damage of each attack in this group, depending on whether it
+                    //lands a critical hit.
+                    let damage_per_attack = if rng.roll_percent() < attack.attack.crit_chance_percent {
+                        attack.attack.crit_damage()
+                    } else {
+                        attack.attack.damage_per_attack
+                    };
+                    
+                    //Attacks which deal no damage are spent without effect.
+                    if damage_per_attack == 0 {
+                        attack.attack.parralel_attacks = 0;
                     //Resolve the damage from this group of attacks against this `Ship`.
                     //If any damage was unused, the number of attacks is set to reflect
                     //this; else its zeroed.
-                    attack.attack.parralel_attacks = self.resolve_damage(attack.attack.sum_damage())
-                        / attack.attack.damage_per_attack;
+                    } else {
+                        attack.attack.parralel_attacks = self.resolve_damage_with(
+                            attack.attack.parralel_attacks.saturating_mul(damage_per_attack),
+                            attack.attack.bypass_shields
+                        ) / damage_per_attack;
+                    }
                 },
                 //Else all the attacks are resolved.
                 None => break
@@ -264,13 +437,85 @@ impl From<Rc<ShipTemplate>> for Ship {
             Self::from_parts(
                 template.clone(),
                 template.get_fuel_capacity(),
-                template.max_hull,
+                template.get_max_hull(),
                 template.get_shield_capacity()
             )
         }
     }
 }
 
+/// The serialized form of a `Ship`, referencing its `ShipTemplate` by name.
+#[derive(Serialize, Deserialize)]
+struct ShipState {
+    /// The name of the `ShipTemplate` the `Ship` is instanciating.
+    template: String,
+    /// The current fuel of the `Ship`.
+    fuel_units: FuelUnit,
+    /// The current hull points of the `Ship`.
+    hull_points: HullPoint,
+    /// The current shield points of the `Ship`.
+    shield_points: ShieldPoint,
+    /// The units of Mass carried by the `Ship`.
+    #[serde(default)]
+    cargo: Mass
+}
+
+impl Serialize for Ship {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        //The template is written by name so it must be loaded.
+        let template = self.template_name()
+        .ok_or_else(|| <S::Error as ser::Error>::custom("the `ShipTemplate` is not loaded"))?;
+        
+        ShipState {
+            template,
+            fuel_units: self.fuel_units,
+            hull_points: self.hull_points,
+            shield_points: self.shield_points,
+            cargo: self.cargo
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ship {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = ShipState::deserialize(deserializer)?;
+        //Resolve the template through the game templates.
+        let template = get_game_templates().get(&state.template)
+        .ok_or_else(|| <D::Error as de::Error>::custom(
+            format!("the `ShipTemplate` \"{}\" could not be loaded", state.template)
+        ))?;
+        
+        //Check that the cargo fits in the template.
+        if state.cargo > template.get_cargo_capacity() {
+            return Err(<D::Error as de::Error>::custom("the cargo exceeds the `ShipTemplate`s capacity"));
+        }
+        
+        Ship::new(template, state.fuel_units, state.hull_points, state.shield_points)
+        .map(|ship| Ship { cargo: state.cargo, ..ship })
+        .map_err(|e| <D::Error as de::Error>::custom(format!("{:?}", e)))
+    }
+}
+
+/// Moves up to `mass` units of cargo from one `Ship` to another and returns the amount
+/// which was moved.
+/// The amount moved is limited by the cargo held by `from` and the space left in `to`.
+///
+/// #Params
+///
+/// from --- The `Ship` to take cargo from.
+/// to --- The `Ship` to give cargo to.
+/// mass --- The units of Mass to attempt to move.
+pub fn transfer_cargo(from: &mut Ship, to: &mut Ship, mass: Mass) -> Mass {
+    //The units of Mass which `to` has room for.
+    let space = to.template.get_cargo_capacity().saturating_sub(to.cargo);
+    //The units of Mass which can be moved.
+    let moved = ::std::cmp::min(mass, ::std::cmp::min(from.cargo, space));
+    
+    from.cargo -= moved;
+    to.cargo += moved;
+    moved
+}
+
 /// Attempts to spawn a new ship.
 /// `None` is returned if a `ShipTemplate` with the passed `typename` is not found.
 ///
@@ -286,6 +531,191 @@ pub fn build_game_ship(typename: &String, faction: factions::Faction) -> Option<
     ))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_resolve_zero_damage_attacks() {
+        let template = shared(test_template());
+        let mut ship = Ship::from(template);
+        let mut attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(10, 0), 1)]);
+        
+        ship.resolve_attacks(&mut attacks);
+        assert!(
+            attacks.iter().all(|attack| attack.attack.parralel_attacks == 0),
+            "`Ship::resolve_attacks` failed to spend zero damage attacks."
+        );
+        assert!(
+            ship.get_hull_points() == 100
+            && ship.get_shield_points() == 100,
+            "`Ship::resolve_attacks` did damage with zero damage attacks."
+        );
+    }
+    
+    #[test]
+    fn test_resolve_crit_attacks() {
+        let template = shared(test_template().shield_capacity(0).shield_recovery(0));
+        let attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 10).with_crit(3, 50).expect("Failed to create attack."), 1)]);
+        
+        let mut ship = Ship::from(template.clone());
+        ship.resolve_attacks_with(&mut attacks.clone(), &mut ForcedRng(0));
+        assert!(ship.get_hull_points() == 70, "`Ship::resolve_attacks_with` failed to land a critical hit.");
+        
+        let mut ship = Ship::from(template.clone());
+        ship.resolve_attacks_with(&mut attacks.clone(), &mut ForcedRng(99));
+        assert!(ship.get_hull_points() == 90, "`Ship::resolve_attacks_with` landed a critical hit on a failed roll.");
+        
+        let mut ship = Ship::from(template);
+        ship.resolve_attacks(&mut attacks.clone());
+        assert!(ship.get_hull_points() == 90, "`Ship::resolve_attacks` landed a critical hit.");
+    }
+    
+    #[test]
+    fn test_bypass_shields() {
+        let template = shared(test_template().shield_capacity(50));
+        let ship = Ship::from(template.clone());
+        
+        assert!(ship.simulate_damage_with(30, false) == (100, 20, 0), "`Ship::simulate_damage_with` ignored shields.");
+        assert!(ship.simulate_damage_with(30, true) == (70, 50, 0), "`Ship::simulate_damage_with` failed to ignore shields.");
+        assert!(ship.simulate_damage_with(130, true) == (0, 50, 30), "`Ship::simulate_damage_with` returned incorrect damage.");
+        
+        let mut ship = Ship::from(template);
+        ship.resolve_attacks(&mut ReducedAttacks::new(vec![
+            TargetedAttack::new(Attack::new(3, 10).with_bypass_shields(true), 1)
+        ]));
+        assert!(
+            ship.get_hull_points() == 70
+            && ship.get_shield_points() == 50,
+            "`Ship::resolve_attacks` failed to bypass shields."
+        );
+    }
+    
+    #[test]
+    fn test_status() {
+        let template = shared(test_template());
+        let status = |hull_points, shield_points| Ship::new(template.clone(), 10, hull_points, shield_points)
+            .expect("`Ship::new` failed to create `Ship`.")
+            .status();
+        
+        assert!(status(0, 100) == Destroyed, "`Ship::status` failed to register destruction.");
+        assert!(status(24, 100) == Critical, "`Ship::status` failed to register critical hull.");
+        assert!(status(25, 100) == Damaged, "`Ship::status` registered a quarter hull as critical.");
+        assert!(status(100, 99) == Damaged, "`Ship::status` failed to register damaged shields.");
+        assert!(status(100, 100) == Pristine, "`Ship::status` failed to register an undamaged `Ship`.");
+    }
+    
+    #[test]
+    fn test_same_type() {
+        let template = shared(test_template());
+        let ship = Ship::new(template.clone(), 10, 100, 100).expect("`Ship::new` failed to create `Ship`.");
+        let damaged = Ship::new(template.clone(), 2, 30, 0).expect("`Ship::new` failed to create `Ship`.");
+        let copy = Ship::from(Rc::new((*template).clone()));
+        let other = Ship::from(shared(test_template().max_hull(200)));
+        
+        assert!(ship != damaged && ship.same_type(&damaged), "`Ship::same_type` compared current state.");
+        assert!(ship.same_type(&copy), "`Ship::same_type` failed on an equal template.");
+        assert!(!ship.same_type(&other), "`Ship::same_type` matched a different template.");
+    }
+    
+    #[test]
+    fn test_transfer_cargo() {
+        let template = shared(test_template().cargo_capacity(50));
+        let mut from = Ship::from(template.clone());
+        let mut to = Ship::from(template);
+        from.cargo = 40;
+        to.cargo = 30;
+        
+        assert!(transfer_cargo(&mut from, &mut to, 30) == 20, "`transfer_cargo` exceeded the capacity of `to`.");
+        assert!(from.get_cargo() == 20 && to.get_cargo() == 50, "`transfer_cargo` moved incorrect cargo.");
+        
+        to.cargo = 10;
+        assert!(transfer_cargo(&mut to, &mut from, 100) == 10, "`transfer_cargo` exceeded the cargo of `from`.");
+        assert!(from.get_cargo() == 30 && to.get_cargo() == 0, "`transfer_cargo` moved incorrect cargo.");
+    }
+    
+    #[test]
+    fn test_load_cargo() {
+        let template = shared(test_template().cargo_capacity(50));
+        let mut ship = Ship::from(template);
+        
+        assert!(ship.load_cargo(30) == Ok(()) && ship.get_cargo() == 30, "`Ship::load_cargo` failed to load cargo.");
+        assert!(ship.load_cargo(21) == Err(CargoError), "`Ship::load_cargo` exceeded the cargo capacity.");
+        assert!(ship.get_cargo() == 30, "`Ship::load_cargo` modified cargo on error.");
+        
+        let smaller = shared(test_template().cargo_capacity(20));
+        assert!(ship.set_template(smaller) == Err(CargoError), "`Ship::set_template` ignored the carried cargo.");
+    }
+    
+    #[test]
+    fn test_repair_hull() {
+        let template = shared(test_template());
+        let mut ship = Ship::new(template, 10, 40, 100).expect("Failed to create `Ship`.");
+        
+        ship.repair_hull(30);
+        assert!(ship.get_hull_points() == 70, "`Ship::repair_hull` restored incorrect hull.");
+        ship.repair_hull(UInt::MAX);
+        assert!(ship.get_hull_points() == 100, "`Ship::repair_hull` exceeded the max hull.");
+    }
+    
+    #[test]
+    fn test_damage_reduction() {
+        let mut ship = Ship::from(shared(test_template().shield_capacity(50)));
+        let mut armoured = Ship::from(shared(test_template().shield_capacity(50).damage_reduction(50)));
+        
+        assert!(ship.simulate_damage(100) == (50, 0, 0), "`Ship::simulate_damage` reduced damage without damage reduction.");
+        assert!(armoured.simulate_damage(100) == (100, 0, 0), "`Ship::simulate_damage` failed to reduce damage.");
+        assert!(
+            armoured.simulate_damage(400) == (0, 0, 100),
+            "`Ship::simulate_damage` returned overkill damage in reduced units."
+        );
+        
+        assert!(
+            test_template().damage_reduction(101).build()
+            .expect_err("`ShipTemplateBuilder::build` failed to error on invalid `damage_reduction`."
+            ) == DefenceError,
+            "`ShipTemplateBuilder::build` returned incorrect `ShipError`."
+        );
+    }
+    
+    #[test]
+    fn test_ship_serialization() {
+        unsafe {
+            init_game_templates();
+        }
+        let template = get_game_templates().insert(
+            String::from("Test Serialization Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let ship = Ship::new(template, 5, 60, 20).expect("`Ship::new` failed to create `Ship`.");
+        
+        let serialized = ::toml::to_string(&ship).expect("Failed to serialize `Ship`.");
+        assert!(
+            serialized.contains("Test Serialization Ship"),
+            "`Ship` failed to serialize its template by name."
+        );
+        
+        let deserialized: Ship = ::toml::from_str(&serialized).expect("Failed to deserialize `Ship`.");
+        assert!(deserialized == ship, "Deserialized `Ship` did not match the original.");
+        assert!(
+            Rc::ptr_eq(&deserialized.template, &ship.template),
+            "Deserialized `Ship` failed to resolve the loaded template."
+        );
+    }
+    
+    #[test]
+    fn test_periods_of_fuel() {
+        let template = shared(test_template().fuel_use(3));
+        assert!(Ship::from(template).periods_of_fuel() == 3, "`Ship::periods_of_fuel` returned an incorrect range.");
+        
+        let template = shared(test_template().fuel_use(0));
+        assert!(
+            Ship::from(template).periods_of_fuel() == UInt::MAX,
+            "`Ship::periods_of_fuel` failed on a `Ship` which uses no fuel."
+        );
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
     // use super::*;
@@ -5,11 +5,30 @@
 //! Author: Daniel Bechaz</br>
 //! Date: 2017/11/06
 
+use std::fmt;
+use std::error::Error;
+
 /// An error type relating to Ships.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum ShipError {
     FuelError,
     ShieldError,
-    HullError
+    HullError,
+    DefenceError,
+    CargoError
 }
 pub use self::ShipError::*;
+
+impl fmt::Display for ShipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            FuelError => "the fuel exceeds the fuel capacity",
+            ShieldError => "the shields exceed the shield capacity",
+            HullError => "the hull exceeds the max hull",
+            DefenceError => "the damage reduction exceeds 100%",
+            CargoError => "the cargo exceeds the cargo capacity"
+        })
+    }
+}
+
+impl Error for ShipError {}
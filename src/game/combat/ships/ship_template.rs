@@ -11,33 +11,38 @@ use super::ship_error::*;
 use super::attacks::*;
 use std::collections::LinkedList;
 use std::sync::*;
-use std::io::{self, Read};
-use std::path::Path;
+use game::file_system::{TomlFileError, read_toml, write_toml};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::cmp::Ordering;
+use std::fmt;
 
 pub type FuelUnit = UInt;
 pub type HullPoint = UInt;
 pub type ShieldPoint = UInt;
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 /// `ShipTemplate` is a representation of a type of Ship.
 pub struct ShipTemplate {
     /// The size class of this Ship type.
-    pub ship_size_class: ShipSize,
+    ship_size_class: ShipSize,
     /// The maximum units of fuel carried by this Ship type.
     fuel_capacity: FuelUnit,
     /// The number of units of fuel used by this Ship type in one period.
     fuel_use: FuelUnit,
     /// The maximum hull points (health points) of this Ship type.
-    pub max_hull: HullPoint,
+    max_hull: HullPoint,
     /// The maximum shield points of this Ship type.
     shield_capacity: ShieldPoint,
     /// The number of shield points regenerated by this Ship type in one period.
     shield_recovery: ShieldPoint,
+    /// The percentage of incoming damage which this Ship type absorbs without consequence.
+    #[serde(default)]
+    damage_reduction: UInt,
     /// The maximum units of Mass this Ship type can transport.
-    pub cargo_capacity: Mass,
+    cargo_capacity: Mass,
     /// The `TargetedAttack`s dealt by this Ship type.
-    pub attacks: ReducedAttacks
+    attacks: ReducedAttacks
 }
 
 impl ShipTemplate {
@@ -48,15 +53,16 @@ impl ShipTemplate {
     /// ship_size_class --- The size class of this Ship type.
     /// fuel_capacity --- The maximum units of fuel carried by this Ship type.
     /// fuel_use --- The number of units of fuel used by this Ship type in one period.
-    /// defence_rating --- The percentage of attacks which this Ship type evades or absorbs without consequence.
     /// hull_points --- The maximum hull points (health points) of this Ship type.
     /// shield_points --- The maximum shield points of this Ship type.
     /// shield_recovery --- The number of shield points regenerated by this Ship type in one period.
+    /// damage_reduction --- The percentage of incoming damage which this Ship type absorbs without consequence.
     /// cargo_capacity --- The maximum units of Mass this Ship type can transport.
     /// attacks --- The `TargetedAttack`s dealt by this Ship type.
+    #[allow(clippy::too_many_arguments)]
     pub unsafe fn from_parts(ship_size_class: ShipSize, fuel_capacity: UInt,
         fuel_use: UInt, max_hull: UInt, shield_capacity: UInt, shield_recovery: UInt,
-        cargo_capacity: Mass, attacks: ReducedAttacks) -> Self {
+        damage_reduction: UInt, cargo_capacity: Mass, attacks: ReducedAttacks) -> Self {
         Self {
             ship_size_class,
             fuel_capacity,
@@ -64,6 +70,7 @@ impl ShipTemplate {
             max_hull,
             shield_capacity,
             shield_recovery,
+            damage_reduction,
             cargo_capacity,
             attacks
         }
@@ -78,15 +85,20 @@ impl ShipTemplate {
     ///
     /// FuelError --- fuel_use > fuel_capacity
     /// ShieldError --- shield_recovery > shield_points
+    /// DefenceError --- damage_reduction > 100
+    #[allow(clippy::too_many_arguments)]
     pub fn new(ship_size_class: ShipSize, fuel_capacity: UInt, fuel_use: UInt,
         hull_points: UInt, shield_points: UInt, shield_recovery: UInt,
-        cargo_capacity: Mass, attacks: ReducedAttacks) -> Result<Self, ShipError> {
+        damage_reduction: UInt, cargo_capacity: Mass, attacks: ReducedAttacks) -> Result<Self, ShipError> {
         //Check that fuel use is not greater than fuel capacity.
         if fuel_use > fuel_capacity {
             Err(FuelError)
         //Check that shield_recovery is not greater than shield capacity
         } else if shield_recovery > shield_points {
             Err(ShieldError)
+        //Check that damage reduction is a valid percentage.
+        } else if damage_reduction > 100 {
+            Err(DefenceError)
         //All checks passed, parameters are valid.
         } else {
             Ok(
@@ -98,6 +110,7 @@ impl ShipTemplate {
                         hull_points,
                         shield_points,
                         shield_recovery,
+                        damage_reduction,
                         cargo_capacity,
                         attacks
                     )
@@ -105,6 +118,22 @@ impl ShipTemplate {
             )
         }
     }
+    /// Returns the `ship_size_class` of this `ShipTemplate`.
+    pub fn get_ship_size_class(&self) -> ShipSize {
+        self.ship_size_class
+    }
+    /// Returns the `max_hull` of this `ShipTemplate`.
+    pub fn get_max_hull(&self) -> HullPoint {
+        self.max_hull
+    }
+    /// Returns the `cargo_capacity` of this `ShipTemplate`.
+    pub fn get_cargo_capacity(&self) -> Mass {
+        self.cargo_capacity
+    }
+    /// Returns the `attacks` of this `ShipTemplate`.
+    pub fn get_attacks(&self) -> &ReducedAttacks {
+        &self.attacks
+    }
     /// Returns the `fuel_capacity` of this `ShipTemplate`.
     pub fn get_fuel_capacity(&self) -> FuelUnit {
         self.fuel_capacity
@@ -169,6 +198,24 @@ impl ShipTemplate {
     pub fn get_shield_recovery(&self) -> ShieldPoint {
         self.shield_recovery
     }
+    /// Returns the number of periods this Ship type takes to regenerate full shields from
+    /// `current` shield points, or `UInt::MAX` if it never regenerates.
+    ///
+    /// #Params
+    ///
+    /// current --- The current shield points of the Ship.
+    pub fn periods_to_full_shield(&self, current: ShieldPoint) -> UInt {
+        let missing = self.shield_capacity.saturating_sub(current);
+        
+        if missing == 0 {
+            0
+        } else if self.shield_recovery == 0 {
+            UInt::MAX
+        } else {
+            //Round up as a partial period still has to pass.
+            missing.div_ceil(self.shield_recovery)
+        }
+    }
     /// Attempts to set the `shield_recovery` of this `ShipTemplate`.
     ///
     /// #Params
@@ -185,6 +232,65 @@ impl ShipTemplate {
             self.shield_recovery = val; Ok(())
         }
     }
+    /// Returns the `damage_reduction` of this `ShipTemplate`.
+    pub fn get_damage_reduction(&self) -> UInt {
+        self.damage_reduction
+    }
+    /// Attempts to set the `damage_reduction` of this `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The new value to set.
+    ///
+    /// #Errors
+    ///
+    /// DefenceError --- val > 100
+    pub fn set_damage_reduction(&mut self, val: UInt) -> Result<(), ShipError> {
+        if val > 100 {
+            Err(DefenceError)
+        } else {
+            self.damage_reduction = val; Ok(())
+        }
+    }
+    /// Blends the passed `ShipTemplate`s into a single `ShipTemplate` whose stats are
+    /// the average of the inputs weighted by their counts.
+    /// The size class and attacks are taken from the most common `ShipTemplate`.
+    /// `None` is returned if there are no `ShipTemplate`s to blend.
+    ///
+    /// #Params
+    ///
+    /// templates --- The `ShipTemplate`s to blend and the number of each.
+    pub fn blend(templates: &[(ShipTemplate, UInt)]) -> Option<ShipTemplate> {
+        //The total number of ships being blended.
+        let total = templates.iter().map(|&(_, count)| count as u64).sum::<u64>();
+        
+        if total == 0 {
+            return None;
+        }
+        
+        //The most common template, preferring the earliest on ties.
+        let common = &templates.iter().rev()
+            .max_by_key(|&&(_, count)| count)
+            .unwrap().0;
+        //Averages a field of the templates weighted by their count.
+        let average = |field: fn(&ShipTemplate) -> UInt| (
+            templates.iter()
+            .map(|&(ref template, count)| field(template) as u64 * count as u64)
+            .sum::<u64>() / total
+        ) as UInt;
+        
+        ShipTemplateBuilder::new()
+        .ship_size_class(common.ship_size_class)
+        .fuel_capacity(average(|template| template.fuel_capacity))
+        .fuel_use(average(|template| template.fuel_use))
+        .max_hull(average(|template| template.max_hull))
+        .shield_capacity(average(|template| template.shield_capacity))
+        .shield_recovery(average(|template| template.shield_recovery))
+        .damage_reduction(average(|template| template.damage_reduction))
+        .cargo_capacity(average(|template| template.cargo_capacity))
+        .attacks(common.attacks.clone())
+        .build().ok()
+    }
     /// Returns true if this `ShipTemplate` can target the passed `ShipTemplate`.
     ///
     /// #Params
@@ -200,12 +306,247 @@ impl ShipTemplate {
             None => false
         }
     }
+    /// Returns true if this `ShipTemplate` has an attack which can target and damage the
+    /// passed `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// defender --- The `ShipTemplate` to attack.
+    pub fn attacks_effective_against(&self, defender: &ShipTemplate) -> bool {
+        self.can_target(defender)
+        && self.attacks.iter()
+            .any(|attack| defender.is_valid_for(attack) && attack.attack.damage_per_attack != 0)
+    }
+    /// Returns the largest of `candidates` which this `ShipTemplate` can attack effectively.
+    ///
+    /// #Params
+    ///
+    /// candidates --- The `ShipTemplate`s to choose from.
+    pub fn best_target<'a>(&self, candidates: &'a [ShipTemplate]) -> Option<&'a ShipTemplate> {
+        candidates.iter()
+        .filter(|candidate| self.attacks_effective_against(candidate))
+        .max()
+    }
+    /// Returns true if this Ship type can be targeted by the passed `TargetedAttack`.
+    ///
+    /// #Params
+    ///
+    /// attack --- The `TargetedAttack` to check.
+    pub fn is_valid_for(&self, attack: &TargetedAttack) -> bool {
+        attack.valid_target(self.ship_size_class)
+    }
+    /// Returns the name, value in this `ShipTemplate` and value in `other` of each field
+    /// which differs between them. Attacks are not compared.
+    ///
+    /// #Params
+    ///
+    /// other --- The `ShipTemplate` to compare against.
+    pub fn diff(&self, other: &ShipTemplate) -> Vec<(&'static str, i64, i64)> {
+        [
+            ("ship_size_class", self.ship_size_class as i64, other.ship_size_class as i64),
+            ("fuel_capacity", self.fuel_capacity as i64, other.fuel_capacity as i64),
+            ("fuel_use", self.fuel_use as i64, other.fuel_use as i64),
+            ("max_hull", self.max_hull as i64, other.max_hull as i64),
+            ("shield_capacity", self.shield_capacity as i64, other.shield_capacity as i64),
+            ("shield_recovery", self.shield_recovery as i64, other.shield_recovery as i64),
+            ("damage_reduction", self.damage_reduction as i64, other.damage_reduction as i64),
+            ("cargo_capacity", self.cargo_capacity as i64, other.cargo_capacity as i64)
+        ].iter()
+        .filter(|&&(_, old, new)| old != new)
+        .cloned().collect()
+    }
+}
+
+impl PartialOrd for ShipTemplate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `ShipTemplate`s are ordered by their size class alone, with ties resolved by max hull.
+/// Equality still compares every field so templates can be equal in ordering but unequal.
+impl Ord for ShipTemplate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        //Ordering is done on the size class.
+        match self.ship_size_class.cmp(&other.ship_size_class) {
+            //Equality is resolved by ordering max hull.
+            Ordering::Equal => self.max_hull.cmp(&other.max_hull),
+            ord => ord
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// `ShipTemplateBuilder` builds a `ShipTemplate` from named fields rather than
+/// positional arguments.
+/// Unset fields default to a size class and hull of 1 and no fuel, shields, damage
+/// reduction, cargo or attacks.
+pub struct ShipTemplateBuilder {
+    ship_size_class: ShipSize,
+    fuel_capacity: FuelUnit,
+    fuel_use: FuelUnit,
+    max_hull: HullPoint,
+    shield_capacity: ShieldPoint,
+    shield_recovery: ShieldPoint,
+    damage_reduction: UInt,
+    cargo_capacity: Mass,
+    attacks: ReducedAttacks
+}
+
+impl ShipTemplateBuilder {
+    /// Returns a new `ShipTemplateBuilder` with every field set to its default.
+    pub fn new() -> Self {
+        Self {
+            ship_size_class: 1,
+            fuel_capacity: 0,
+            fuel_use: 0,
+            max_hull: 1,
+            shield_capacity: 0,
+            shield_recovery: 0,
+            damage_reduction: 0,
+            cargo_capacity: 0,
+            attacks: ReducedAttacks::new(Vec::new())
+        }
+    }
+    /// Sets the `ship_size_class` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The size class of this Ship type.
+    pub fn ship_size_class(mut self, val: ShipSize) -> Self {
+        self.ship_size_class = val; self
+    }
+    /// Sets the `fuel_capacity` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The maximum units of fuel carried by this Ship type.
+    pub fn fuel_capacity(mut self, val: FuelUnit) -> Self {
+        self.fuel_capacity = val; self
+    }
+    /// Sets the `fuel_use` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The number of units of fuel used by this Ship type in one period.
+    pub fn fuel_use(mut self, val: FuelUnit) -> Self {
+        self.fuel_use = val; self
+    }
+    /// Sets the `max_hull` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The maximum hull points (health points) of this Ship type.
+    pub fn max_hull(mut self, val: HullPoint) -> Self {
+        self.max_hull = val; self
+    }
+    /// Sets the `shield_capacity` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The maximum shield points of this Ship type.
+    pub fn shield_capacity(mut self, val: ShieldPoint) -> Self {
+        self.shield_capacity = val; self
+    }
+    /// Sets the `shield_recovery` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The number of shield points regenerated by this Ship type in one period.
+    pub fn shield_recovery(mut self, val: ShieldPoint) -> Self {
+        self.shield_recovery = val; self
+    }
+    /// Sets the `damage_reduction` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The percentage of incoming damage which this Ship type absorbs without consequence.
+    pub fn damage_reduction(mut self, val: UInt) -> Self {
+        self.damage_reduction = val; self
+    }
+    /// Sets the `cargo_capacity` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The maximum units of Mass this Ship type can transport.
+    pub fn cargo_capacity(mut self, val: Mass) -> Self {
+        self.cargo_capacity = val; self
+    }
+    /// Sets the `attacks` of the built `ShipTemplate`.
+    ///
+    /// #Params
+    ///
+    /// val --- The `TargetedAttack`s dealt by this Ship type.
+    pub fn attacks(mut self, val: ReducedAttacks) -> Self {
+        self.attacks = val; self
+    }
+    /// Attempts to build the `ShipTemplate` through `ShipTemplate::new`.
+    ///
+    /// #Errors
+    ///
+    /// Refer to `ShipTemplate::new` for errors.
+    pub fn build(self) -> Result<ShipTemplate, ShipError> {
+        ShipTemplate::new(
+            self.ship_size_class,
+            self.fuel_capacity,
+            self.fuel_use,
+            self.max_hull,
+            self.shield_capacity,
+            self.shield_recovery,
+            self.damage_reduction,
+            self.cargo_capacity,
+            self.attacks
+        )
+    }
+}
+
+impl Default for ShipTemplateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns a `ShipTemplateBuilder` for the `ShipTemplate` shared by the tests.
+#[cfg(test)]
+pub fn test_template() -> ShipTemplateBuilder {
+    ShipTemplateBuilder::new()
+    .fuel_capacity(10)
+    .fuel_use(1)
+    .max_hull(100)
+    .shield_capacity(100)
+    .shield_recovery(1)
+}
+
+/// Builds a shared `ShipTemplate` from `builder` for the tests.
+#[cfg(test)]
+pub fn shared(builder: ShipTemplateBuilder) -> Rc<ShipTemplate> {
+    Rc::new(builder.build().expect("Failed to create template."))
 }
 
 #[derive(Debug, Eq, Clone)]
 /// A `ShipTemplate` with a name.
 pub struct NamedTemplate(String, Rc<ShipTemplate>);
 
+impl NamedTemplate {
+    /// Returns a new `NamedTemplate`.
+    ///
+    /// #Params
+    ///
+    /// name --- The name of the `ShipTemplate`.
+    /// template --- The `ShipTemplate`.
+    pub fn new(name: String, template: ShipTemplate) -> Self {
+        NamedTemplate(name, Rc::new(template))
+    }
+    /// Returns the name of this `NamedTemplate`.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+    /// Returns the `ShipTemplate` of this `NamedTemplate`.
+    pub fn template(&self) -> &Rc<ShipTemplate> {
+        &self.1
+    }
+}
+
 impl PartialEq for NamedTemplate {
     fn eq(&self, other: &Self) -> bool {
         self.1 == other.1
@@ -240,7 +581,8 @@ pub struct TemplateBuf {
     pub expected_load: usize
 }
 
-static SHIPS_DIR: &str = "./res/ships/";
+/// The directory `.ship` files are loaded from.
+pub static SHIPS_DIR: &str = "./res/ships/";
 
 impl TemplateBuf {
     /// Builds a `TemplateBuf` from raw parts.
@@ -270,7 +612,7 @@ impl TemplateBuf {
     /// Unloads all templates which are over `expected_load` and have no live references.
     pub fn unload_overflow(&mut self) {
         //The number of templates over the `expected_load`.
-        let mut to_unload = self.loaded() - self.expected_load;
+        let mut to_unload = self.loaded().saturating_sub(self.expected_load);
         //The maximum number of iterations which can to be done.
         let mut iterated = self.loaded();
         
@@ -323,6 +665,65 @@ impl TemplateBuf {
         
         return false;
     }
+    /// Returns the name of the passed `ShipTemplate` if it is loaded in this `TemplateBuf`.
+    ///
+    /// #Params
+    ///
+    /// template --- The loaded `ShipTemplate` to find the name of.
+    pub fn name_of(&self, template: &Rc<ShipTemplate>) -> Option<&String> {
+        self.templates.iter()
+        .find(|named| Rc::ptr_eq(&named.1, template))
+        .map(|named| &named.0)
+    }
+    /// Loads the passed `ShipTemplate` into this `TemplateBuf` under `name`, replacing
+    /// any `ShipTemplate` already loaded with the same name.
+    /// A replaced `ShipTemplate` with live references stays loaded, so that its `Ship`s
+    /// can still be named, until it is unloaded as overflow.
+    ///
+    /// #Params
+    ///
+    /// name --- The name of the `ShipTemplate`.
+    /// template --- The `ShipTemplate` to load.
+    pub fn insert(&mut self, name: String, template: ShipTemplate) -> Rc<ShipTemplate> {
+        //Remove any unused template loaded under the same name.
+        let templates = ::std::mem::take(&mut self.templates);
+        self.templates = templates.into_iter()
+        .filter(|named| named.0 != name || Rc::strong_count(&named.1) > 1)
+        .collect();
+        
+        self.templates.push_front(Box::new(NamedTemplate::new(name, template)));
+        self.templates.front().unwrap().1.clone()
+    }
+    /// Attempts to load the `ShipTemplate` of the given name from its `.ship` file,
+    /// replacing the `ShipTemplate` in the buffer if it is loaded.
+    /// Existing references to the replaced `ShipTemplate` keep the old values.
+    ///
+    /// #Params
+    ///
+    /// name --- The name of the `ShipTemplate` to reload.
+    ///
+    /// #Errors
+    ///
+    /// Refer to `TemplateLoadError` for errors.
+    pub fn reload(&mut self, name: &String) -> Result<Rc<ShipTemplate>, TemplateLoadError> {
+        self.reload_from(name, &template_path(name))
+    }
+    /// Attempts to load the `ShipTemplate` of the given name from `file_path`, replacing
+    /// the `ShipTemplate` in the buffer if it is loaded.
+    ///
+    /// #Params
+    ///
+    /// name --- The name of the `ShipTemplate` to reload.
+    /// file_path --- The path to the `.ship` file to load.
+    fn reload_from(&mut self, name: &String, file_path: &Path) -> Result<Rc<ShipTemplate>, TemplateLoadError> {
+        let template = load_valid_template(file_path)?;
+        
+        eprintln!("\"{}\" has been reloaded.", name);
+        let template = self.insert(name.clone(), template);
+        //Replaced templates whose `Ship`s have all gone are unloaded.
+        self.unload_overflow();
+        Ok(template)
+    }
     /// Attempts to get the `ShipTemplate` of the given name.
     /// If the template is not in the buffer it will attempt to be loaded.
     ///
@@ -337,18 +738,13 @@ impl TemplateBuf {
         
         //If the template was not found, attempt to load and return it.
         res.or_else(|| {
-            //Build a path to the `.ship` file.
-            let mut file_path = String::from(SHIPS_DIR);
-            file_path.push_str(name);
-            file_path.push_str(".ship");
-            
-            //Attempt to load the template.
-            match load_template(file_path.as_ref()) {
+            //Attempt to load and validate the template.
+            match load_valid_template(&template_path(name)) {
                 //If the template was loaded successfully.
                 Ok(template) => {
                     //Store it on the heap and keep a reference in the buffer.
                     self.templates.push_front(
-                        Box::new(NamedTemplate(name.clone(), Rc::new(template)))
+                        Box::new(NamedTemplate::new(name.clone(), template))
                     );
                     eprintln!("\"{}\" has been loaded.", name);
                     //Return the new template.
@@ -361,31 +757,126 @@ impl TemplateBuf {
     }
 }
 
+/// Builds the path to the `.ship` file of the `ShipTemplate` with the passed name.
+///
+/// #Params
+///
+/// name --- The name of the `ShipTemplate`.
+fn template_path(name: &str) -> PathBuf {
+    let mut file_path = String::from(SHIPS_DIR);
+    file_path.push_str(name);
+    file_path.push_str(".ship");
+    
+    PathBuf::from(file_path)
+}
+
 /// Attempt to load a `ShipTemplate` from a `.ship` file.
 ///
 /// #Params
 ///
 /// file_path --- The path to the `.ship` file to load. 
-fn load_template(file_path: &Path) -> Result<ShipTemplate, Result<io::Error, ::toml::de::Error>> {
+fn load_template(file_path: &Path) -> Result<ShipTemplate, TomlFileError> {
     eprintln!("Loading {:?}...", file_path);
-    //Open the file...
-    ::std::fs::File::open(file_path)
-    .and_then(|mut file| {
-        //Create a buffer for the content.
-        let mut content = String::new();
-        
-        //Read in the content and return it.
-        file.read_to_string(&mut content)
-        .map(|_| content)
-    //Map error values to the result type.
-    }).map_err(|e| Ok(e)
-    //If the reading succeeded, attempt to enterperate the `.ship` file...
-    ).and_then(|content| ::toml::from_str(content.as_str()
-        //Map error values to the result type.
-        ).map_err(|e| Err(e))
-    )
+    read_toml(file_path)
 }
 
+/// Attempt to save a `ShipTemplate` to a `.ship` file.
+///
+/// #Params
+///
+/// template --- The `ShipTemplate` to save.
+/// file_path --- The path to the `.ship` file to save to.
+pub fn save_template(template: &ShipTemplate, file_path: &Path) -> Result<(), TomlFileError> {
+    eprintln!("Saving {:?}...", file_path);
+    write_toml(file_path, template)
+}
+
+/// An error from loading a `ShipTemplate` from a `.ship` file.
+#[derive(Debug)]
+pub enum TemplateLoadError {
+    /// The file could not be read or interpreted.
+    FileError(TomlFileError),
+    /// The file was read but the `ShipTemplate` fails the checks of `ShipTemplate::new`.
+    InvalidTemplate(ShipError)
+}
+pub use self::TemplateLoadError::*;
+
+impl fmt::Display for TemplateLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FileError(ref e) => e.fmt(f),
+            InvalidTemplate(ref e) => e.fmt(f)
+        }
+    }
+}
+
+impl From<TomlFileError> for TemplateLoadError {
+    fn from(e: TomlFileError) -> Self {
+        FileError(e)
+    }
+}
+
+impl From<ShipError> for TemplateLoadError {
+    fn from(e: ShipError) -> Self {
+        InvalidTemplate(e)
+    }
+}
+
+/// Attempt to load and validate a `ShipTemplate` from a `.ship` file.
+///
+/// #Params
+///
+/// file_path --- The path to the `.ship` file to load.
+fn load_valid_template(file_path: &Path) -> Result<ShipTemplate, TemplateLoadError> {
+    let template = load_template(file_path)?;
+    
+    //Rebuild the template through `ShipTemplateBuilder` to run its checks.
+    ShipTemplateBuilder::new()
+    .ship_size_class(template.ship_size_class)
+    .fuel_capacity(template.fuel_capacity)
+    .fuel_use(template.fuel_use)
+    .max_hull(template.max_hull)
+    .shield_capacity(template.shield_capacity)
+    .shield_recovery(template.shield_recovery)
+    .damage_reduction(template.damage_reduction)
+    .cargo_capacity(template.cargo_capacity)
+    .attacks(template.attacks)
+    .build().map_err(TemplateLoadError::from)
+}
+
+/// Loads every `.ship` file in a directory, returning the valid `ShipTemplate`s named
+/// after their files and the name of each file which failed with its error.
+/// Both lists are sorted by name.
+///
+/// #Params
+///
+/// dir --- The directory to search for `.ship` files.
+pub fn load_all_templates(dir: &Path) -> (Vec<NamedTemplate>, Vec<(String, TemplateLoadError)>) {
+    let mut templates = Vec::new();
+    let mut errors = Vec::new();
+    
+    let mut paths = match ::std::fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ship"))
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            errors.push((dir.to_string_lossy().into_owned(), FileError(e.into())));
+            return (templates, errors)
+        }
+    };
+    paths.sort();
+    
+    for path in paths {
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        
+        match load_valid_template(&path) {
+            Ok(template) => templates.push(NamedTemplate::new(name, template)),
+            Err(e) => errors.push((name, e))
+        }
+    }
+    
+    (templates, errors)
+}
 
 static mut GAME_TEMPLATES: *mut Mutex<TemplateBuf> = 0 as *mut Mutex<TemplateBuf>;
 static INIT_GAME_TEMPLATES: Once = ONCE_INIT;
@@ -402,6 +893,329 @@ pub fn get_game_templates() -> MutexGuard<'static, TemplateBuf> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_blend() {
+        let attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 10), 1)]);
+        let common = test_template()
+            .fuel_use(2)
+            .shield_recovery(2)
+            .damage_reduction(20)
+            .attacks(attacks.clone())
+            .build().expect("Failed to create template.");
+        let rare = test_template()
+            .ship_size_class(2)
+            .fuel_capacity(20)
+            .fuel_use(4)
+            .max_hull(200)
+            .shield_capacity(50)
+            .damage_reduction(60)
+            .cargo_capacity(10)
+            .build().expect("Failed to create template.");
+        
+        let blend = ShipTemplate::blend(&[(rare.clone(), 1), (common.clone(), 3)]);
+        assert!(
+            blend == Some(
+                test_template()
+                    .fuel_capacity(12)
+                    .fuel_use(2)
+                    .max_hull(125)
+                    .shield_capacity(87)
+                    .damage_reduction(30)
+                    .cargo_capacity(2)
+                    .attacks(attacks)
+                    .build().expect("Failed to create template.")
+            ),
+            "`ShipTemplate::blend` returned incorrect `ShipTemplate`."
+        );
+        
+        assert!(ShipTemplate::blend(&[]) == None, "`ShipTemplate::blend` failed to error on no templates.");
+        assert!(
+            ShipTemplate::blend(&[(common, 0), (rare, 0)]) == None,
+            "`ShipTemplate::blend` failed to error on zero ships."
+        );
+    }
+    
+    #[test]
+    fn test_diff() {
+        let original = test_template().build().expect("Failed to create template.");
+        let refit = test_template().fuel_capacity(15).max_hull(150).build().expect("Failed to create template.");
+        
+        assert!(
+            original.diff(&refit) == vec![("fuel_capacity", 10, 15), ("max_hull", 100, 150)],
+            "`ShipTemplate::diff` returned incorrect differences."
+        );
+        assert!(original.diff(&original).is_empty(), "`ShipTemplate::diff` found differences in identical templates.");
+    }
+    
+    #[test]
+    fn test_builder() {
+        let attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 10), 1)]);
+        let built = ShipTemplateBuilder::new()
+            .ship_size_class(3)
+            .fuel_capacity(20)
+            .fuel_use(2)
+            .max_hull(150)
+            .shield_capacity(50)
+            .shield_recovery(5)
+            .damage_reduction(10)
+            .cargo_capacity(30)
+            .attacks(attacks.clone())
+            .build();
+        
+        assert!(
+            built == ShipTemplate::new(3, 20, 2, 150, 50, 5, 10, 30, attacks),
+            "`ShipTemplateBuilder::build` built an incorrect `ShipTemplate`."
+        );
+        assert!(
+            ShipTemplateBuilder::new().fuel_use(5).build() == Err(FuelError),
+            "`ShipTemplateBuilder::build` failed to validate the `ShipTemplate`."
+        );
+    }
+    
+    #[test]
+    fn test_accessors() {
+        let attacks = ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 10), 1)]);
+        let mut template = test_template()
+            .ship_size_class(3)
+            .fuel_capacity(20)
+            .fuel_use(2)
+            .max_hull(150)
+            .shield_capacity(50)
+            .shield_recovery(5)
+            .damage_reduction(10)
+            .cargo_capacity(30)
+            .attacks(attacks.clone())
+            .build().expect("Failed to create template.");
+        
+        assert!(
+            template.get_ship_size_class() == 3
+            && template.get_fuel_capacity() == 20
+            && template.get_fuel_use() == 2
+            && template.get_max_hull() == 150
+            && template.get_shield_capacity() == 50
+            && template.get_shield_recovery() == 5
+            && template.get_damage_reduction() == 10
+            && template.get_cargo_capacity() == 30
+            && *template.get_attacks() == attacks,
+            "`ShipTemplate` accessors returned incorrect values."
+        );
+        assert!(template.set_fuel_use(21) == Err(FuelError), "`ShipTemplate::set_fuel_use` broke an invariant.");
+        assert!(template.set_shield_capacity(4) == Err(ShieldError), "`ShipTemplate::set_shield_capacity` broke an invariant.");
+        assert!(template.set_fuel_use(5) == Ok(()) && template.get_fuel_use() == 5, "`ShipTemplate::set_fuel_use` failed.");
+    }
+    
+    #[test]
+    fn test_periods_to_full_shield() {
+        let template = test_template().shield_recovery(10).build().expect("Failed to create template.");
+        assert!(template.periods_to_full_shield(50) == 5, "`ShipTemplate::periods_to_full_shield` failed an exact division.");
+        assert!(template.periods_to_full_shield(45) == 6, "`ShipTemplate::periods_to_full_shield` failed to round up.");
+        assert!(template.periods_to_full_shield(100) == 0, "`ShipTemplate::periods_to_full_shield` failed on full shields.");
+        
+        let template = test_template().shield_recovery(0).build().expect("Failed to create template.");
+        assert!(
+            template.periods_to_full_shield(50) == UInt::MAX,
+            "`ShipTemplate::periods_to_full_shield` failed on zero recovery."
+        );
+    }
+    
+    #[test]
+    fn test_is_valid_for() {
+        let attack = TargetedAttack::new(Attack::new(1, 10), 3);
+        let small = test_template().ship_size_class(2).build().expect("Failed to create template.");
+        let large = test_template().ship_size_class(3).build().expect("Failed to create template.");
+        
+        assert!(large.is_valid_for(&attack), "`ShipTemplate::is_valid_for` rejected a valid target.");
+        assert!(!small.is_valid_for(&attack), "`ShipTemplate::is_valid_for` accepted a target which is too small.");
+    }
+    
+    #[test]
+    fn test_best_target() {
+        let template = |ship_size_class, max_hull| test_template()
+            .ship_size_class(ship_size_class)
+            .max_hull(max_hull)
+            .build().expect("Failed to create template.");
+        let attacker = test_template()
+            .attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 10), 3)]))
+            .build().expect("Failed to create template.");
+        let harmless = test_template()
+            .attacks(ReducedAttacks::new(vec![TargetedAttack::new(Attack::new(1, 0), 1)]))
+            .build().expect("Failed to create template.");
+        let candidates = vec![template(1, 500), template(3, 100), template(5, 200), template(2, 900)];
+        
+        assert!(!attacker.attacks_effective_against(&candidates[0]), "`ShipTemplate::attacks_effective_against` accepted an untargetable defender.");
+        assert!(attacker.attacks_effective_against(&candidates[1]), "`ShipTemplate::attacks_effective_against` rejected a targetable defender.");
+        assert!(!harmless.attacks_effective_against(&candidates[0]), "`ShipTemplate::attacks_effective_against` accepted an attack without damage.");
+        assert!(attacker.best_target(&candidates) == Some(&candidates[2]), "`ShipTemplate::best_target` failed to pick the largest target.");
+        assert!(attacker.best_target(&candidates[..1]) == None, "`ShipTemplate::best_target` picked an untargetable defender.");
+    }
+    
+    #[test]
+    fn test_template_ordering() {
+        let template = |ship_size_class, max_hull| test_template()
+            .ship_size_class(ship_size_class)
+            .max_hull(max_hull)
+            .build().expect("Failed to create template.");
+        let mut templates = vec![template(1, 100), template(5, 50), template(1, 200)];
+        
+        templates.sort_by(|a, b| b.cmp(a));
+        assert!(
+            templates == vec![template(5, 50), template(1, 200), template(1, 100)],
+            "`ShipTemplate` ordering failed to sort by size class then max hull."
+        );
+    }
+    
+    #[test]
+    fn test_named_template() {
+        let template = test_template().build().expect("Failed to create template.");
+        let named = NamedTemplate::new(String::from("Test Named"), template.clone());
+        
+        assert!(named.name() == "Test Named", "`NamedTemplate::name` returned incorrect name.");
+        assert!(**named.template() == template, "`NamedTemplate::template` returned incorrect template.");
+        assert!(
+            named == NamedTemplate::new(String::from("Other Name"), template),
+            "`NamedTemplate` equality compared names."
+        );
+    }
+    
+    #[test]
+    fn test_save_template() {
+        let file_path = ::std::env::temp_dir().join("the_brass_test_save_template.ship");
+        let template = test_template()
+            .ship_size_class(2)
+            .shield_capacity(50)
+            .shield_recovery(5)
+            .damage_reduction(10)
+            .cargo_capacity(3)
+            .attacks(ReducedAttacks::new(vec![
+                TargetedAttack::new(Attack::new(2, 10), 1),
+                TargetedAttack::new(Attack::new(1, 30).with_crit(2, 10).expect("Failed to create attack.").with_bypass_shields(true), 2)
+            ]))
+            .build().expect("Failed to create template.");
+        
+        save_template(&template, &file_path).expect("`save_template` failed to save template.");
+        let loaded = load_template(&file_path).expect("`load_template` failed to load saved template.");
+        ::std::fs::remove_file(&file_path).ok();
+        
+        assert!(loaded == template, "`load_template` did not load the saved template.");
+    }
+    
+    #[test]
+    fn test_reload() {
+        let file_path = ::std::env::temp_dir().join("the_brass_test_reload.ship");
+        let write_template = |max_hull: HullPoint| {
+            let content = format!(
+                "ship_size_class = 1\nfuel_capacity = 10\nfuel_use = 1\nmax_hull = {}\n\
+                shield_capacity = 100\nshield_recovery = 1\ncargo_capacity = 0\n\n[attacks]\nattacks = []\n",
+                max_hull
+            );
+            ::std::fs::write(&file_path, content).expect("Failed to write template.");
+        };
+        let name = String::from("Test Reload");
+        let mut templates = TemplateBuf::with_capacity(1);
+        
+        write_template(100);
+        let old = templates.reload_from(&name, &file_path).expect("`TemplateBuf::reload_from` failed to load template.");
+        assert!(old.max_hull == 100, "`TemplateBuf::reload_from` loaded incorrect template.");
+        
+        write_template(200);
+        let new = templates.reload_from(&name, &file_path).expect("`TemplateBuf::reload_from` failed to reload template.");
+        assert!(new.max_hull == 200, "`TemplateBuf::reload_from` failed to pick up the edited template.");
+        assert!(old.max_hull == 100, "`TemplateBuf::reload_from` modified an existing reference.");
+        assert!(
+            templates.get(&name).is_some_and(|template| Rc::ptr_eq(&template, &new)),
+            "`TemplateBuf::reload_from` failed to replace the old template."
+        );
+        assert!(templates.name_of(&old) == Some(&name), "`TemplateBuf::reload_from` lost the name of a live template.");
+        
+        drop(old);
+        drop(new);
+        write_template(300);
+        templates.reload_from(&name, &file_path).expect("`TemplateBuf::reload_from` failed to reload template.");
+        assert!(templates.loaded() == 1, "`TemplateBuf::reload_from` kept unused replaced templates.");
+        
+        ::std::fs::write(&file_path, "ship_size_class = 1\nfuel_capacity = 10\nfuel_use = 20\nmax_hull = 100\n\
+            shield_capacity = 100\nshield_recovery = 1\ncargo_capacity = 0\n\n[attacks]\nattacks = []\n"
+        ).expect("Failed to write template.");
+        let invalid = templates.reload_from(&name, &file_path);
+        ::std::fs::remove_file(&file_path).ok();
+        match invalid {
+            Err(InvalidTemplate(FuelError)) => (),
+            _ => panic!("`TemplateBuf::reload_from` accepted an invalid template.")
+        }
+        assert!(
+            templates.get(&name).is_some_and(|template| template.max_hull == 300),
+            "`TemplateBuf::reload_from` replaced a template with an invalid one."
+        );
+    }
+    
+    #[test]
+    fn test_unload_overflow_under_load() {
+        let mut templates = TemplateBuf::with_capacity(4);
+        templates.insert(String::from("Test Overflow"), test_template().build().expect("Failed to create template."));
+        
+        templates.unload_overflow();
+        assert!(templates.loaded() == 1, "`TemplateBuf::unload_overflow` unloaded a template under the expected load.");
+    }
+    
+    #[test]
+    fn test_load_valid_template() {
+        let file_path = ::std::env::temp_dir().join("the_brass_test_load_valid_template.ship");
+        ::std::fs::write(
+            &file_path,
+            "ship_size_class = 1\nfuel_capacity = 10\nfuel_use = 1\nmax_hull = 100\n\
+            shield_capacity = 100\nshield_recovery = 1\ndamage_reduction = 150\ncargo_capacity = 0\n\n\
+            [attacks]\nattacks = []\n"
+        ).expect("Failed to write template.");
+        
+        let loaded = load_valid_template(&file_path);
+        ::std::fs::remove_file(&file_path).ok();
+        
+        match loaded {
+            Err(InvalidTemplate(DefenceError)) => (),
+            _ => panic!("`load_valid_template` accepted an invalid `damage_reduction`.")
+        }
+    }
+    
+    #[test]
+    fn test_load_all_templates() {
+        let dir = ::std::env::temp_dir().join("the_brass_test_load_all_templates");
+        ::std::fs::create_dir_all(&dir).expect("Failed to create directory.");
+        let write_template = |name: &str, fuel_use: FuelUnit| {
+            let content = format!(
+                "ship_size_class = 1\nfuel_capacity = 10\nfuel_use = {}\nmax_hull = 100\n\
+                shield_capacity = 100\nshield_recovery = 1\ncargo_capacity = 0\n\n[attacks]\nattacks = []\n",
+                fuel_use
+            );
+            ::std::fs::write(dir.join(name), content).expect("Failed to write template.");
+        };
+        write_template("Good.ship", 1);
+        write_template("Invalid.ship", 20);
+        write_template("Ignored.txt", 1);
+        ::std::fs::write(dir.join("Malformed.ship"), "ship_size_class = \"big\"\n").expect("Failed to write template.");
+        
+        let (templates, errors) = load_all_templates(&dir);
+        ::std::fs::remove_dir_all(&dir).ok();
+        
+        assert!(
+            templates.len() == 1 && templates[0].name() == "Good"
+            && templates[0].template().get_fuel_use() == 1,
+            "`load_all_templates` failed to load the valid template."
+        );
+        assert!(errors.len() == 2, "`load_all_templates` returned incorrect failures.");
+        match (&errors[0], &errors[1]) {
+            (&(ref invalid, InvalidTemplate(FuelError)), &(ref malformed, FileError(::game::file_system::DeserializeError(_)))) => assert!(
+                invalid == "Invalid" && malformed == "Malformed",
+                "`load_all_templates` named the failures incorrectly."
+            ),
+            _ => panic!("`load_all_templates` returned incorrect errors.")
+        }
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
     // use super::*;
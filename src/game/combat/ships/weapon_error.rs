@@ -0,0 +1,15 @@
+//! `weapon_error` defines an Error enum with regards to attacks.
+//!
+//! #Last Modified
+//!
+//! Author: Daniel Bechaz</br>
+//! Date: 2017/11/10
+
+/// An error type relating to attacks.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WeaponError {
+    AttacksError,
+    DamageError,
+    CritError
+}
+pub use self::WeaponError::*;
@@ -13,20 +13,50 @@ use std::ops::Deref;
 
 pub type Faction = UInt;
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 /// `Relation` defines how two `Faction`s feel about each other.
+/// `Relation`s are ordered by hostility, from `Friendly` to `Enemy`, so that the `max`
+/// of two `Relation`s is the most hostile.
 pub enum Relation {
-    /// The `Faction`s are unaware that the other exists.
-    Unaware,
-    /// The `Faction`s are neutral towards each other.
-    Neutral,
     /// The `Faction`s are friendly with each other.
     Friendly,
+    /// The `Faction`s are neutral towards each other.
+    Neutral,
+    /// The `Faction`s are unaware that the other exists.
+    Unaware,
     /// The `Faction`s are an enemy of the other.
     Enemy
 }
 pub use self::Relation::*;
 
+/// Returns true if diplomacy can move a `Relation` from `from` to `to`.
+/// War can always be declared but `Faction`s must be `Neutral` before becoming
+/// `Friendly` and cannot become `Unaware` of each other.
+///
+/// #Params
+///
+/// from --- The current `Relation`.
+/// to --- The new `Relation`.
+pub fn can_transition(from: Relation, to: Relation) -> bool {
+    match (from, to) {
+        (from, to) if from == to => true,
+        (_, Enemy) => true,
+        (_, Unaware) => false,
+        (Unaware, Friendly) => false,
+        _ => true
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// An error type relating to changing `Relation`s.
+pub enum RelationError {
+    /// A `Faction` cannot have a `Relation` with itself.
+    SelfRelationError,
+    /// The `Relation` cannot move to the new `Relation` directly.
+    TransitionError
+}
+pub use self::RelationError::*;
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 /// Defines a pair of `Faction` values. When comparing (A, B) == (B, A).
 pub struct FactionPair(Faction, Faction);
@@ -60,11 +90,24 @@ impl FactionPair {
             })
         }
     }
+    /// Returns the other `Faction` in this `FactionPair` if `faction` is one of the pair.
+    ///
+    /// #Params
+    ///
+    /// faction --- The `Faction` to find the partner of.
+    pub fn other(&self, faction: Faction) -> Option<Faction> {
+        if self.0 == faction {
+            Some(self.1)
+        } else if self.1 == faction {
+            Some(self.0)
+        } else {
+            None
+        }
+    }
     /// Converts the `FactionPair` to a u64.
     pub fn as_u64(&self) -> u64 {
-        unsafe {
-            *(self as *const FactionPair as *const u64)
-        }
+        //`FactionPair` is only aligned to `Faction` so the bits are combined by hand.
+        self.0 as u64 | (self.1 as u64) << 32
     }
 }
 
@@ -78,6 +121,17 @@ impl Hash for FactionPair {
 /// An item which is alligned with a particular faction.
 pub struct AllignedInstance<T: Sized>(pub Faction, pub T);
 
+impl<T: Sized> AllignedInstance<T> {
+    /// Converts the item of this `AllignedInstance` while keeping its `Faction`.
+    ///
+    /// #Params
+    ///
+    /// f --- The conversion to apply to the item.
+    pub fn map<U: Sized, F: FnOnce(T) -> U>(self, f: F) -> AllignedInstance<U> {
+        AllignedInstance(self.0, f(self.1))
+    }
+}
+
 impl<T: Sized> Deref for AllignedInstance<T> {
     type Target = T;
     
@@ -100,6 +154,99 @@ pub fn get_game_factions() -> &'static mut (Vec<String>, HashMap<FactionPair, Re
     }
 }
 
+/// Tallies the number of `Faction`s in each `Relation` with `core`.
+/// `Faction`s which `core` is `Unaware` of are not counted.
+///
+/// #Params
+///
+/// relations --- The `Relation`s between each `FactionPair`.
+/// core --- The `Faction` to count the `Relation`s of.
+pub fn count_by_relation(relations: &HashMap<FactionPair, Relation>, core: Faction) -> HashMap<Relation, usize> {
+    let mut counts = HashMap::new();
+    
+    relations.iter()
+    //Only count the pairs which include `core` and which are aware of each other.
+    .filter(|&(pair, relation)| pair.other(core).is_some() && *relation != Unaware)
+    .for_each(|(_, relation)| *counts.entry(*relation).or_insert(0) += 1);
+    
+    counts
+}
+
+/// Sets the `Relation` between `core` and each `Faction` in `iter`, returning the prior
+/// `Relation` of each `Faction` which was set.
+/// Entries relating `core` to itself are skipped.
+///
+/// #Params
+///
+/// relations --- The `Relation`s between each `FactionPair`.
+/// core --- The `Faction` to set the `Relation`s of.
+/// iter --- The `Faction`s and their new `Relation`s with `core`.
+pub fn set_relations<I: IntoIterator<Item = (Faction, Relation)>>(relations: &mut HashMap<FactionPair, Relation>,
+    core: Faction, iter: I) -> Vec<(Faction, Relation)> {
+    iter.into_iter()
+    //`FactionPair::new` fails to create a pair of `core` with itself.
+    .filter_map(|(faction, relation)| FactionPair::new(core, faction)
+        //Absent `Relation`s are `Unaware`.
+        .map(|pair| (faction, relations.insert(pair, relation).unwrap_or(Unaware)))
+    ).collect()
+}
+
+/// Returns true unless `core` is `Unaware` of `faction`.
+/// A `Faction` is always aware of itself.
+///
+/// #Params
+///
+/// relations --- The `Relation`s between each `FactionPair`.
+/// core --- The `Faction` whose awareness is checked.
+/// faction --- The `Faction` which `core` may be aware of.
+pub fn is_aware(relations: &HashMap<FactionPair, Relation>, core: Faction, faction: Faction) -> bool {
+    match FactionPair::new(core, faction) {
+        //Absent `Relation`s are `Unaware`.
+        Some(pair) => relations.get(&pair).is_some_and(|relation| *relation != Unaware),
+        None => true
+    }
+}
+
+/// Removes the `Relation` between `core` and `faction` entirely, returning the prior
+/// `Relation` if there was one.
+/// Unlike setting `Unaware` this leaves no entry for the pair.
+///
+/// #Params
+///
+/// relations --- The `Relation`s between each `FactionPair`.
+/// core --- The `Faction` which forgets `faction`.
+/// faction --- The `Faction` to forget.
+pub fn forget(relations: &mut HashMap<FactionPair, Relation>, core: Faction, faction: Faction) -> Option<Relation> {
+    FactionPair::new(core, faction).and_then(|pair| relations.remove(&pair))
+}
+
+/// Attempts to set the `Relation` between two `Faction`s, returning the prior `Relation`.
+///
+/// #Params
+///
+/// relations --- The `Relation`s between each `FactionPair`.
+/// first --- The first `Faction` of the pair.
+/// second --- The second `Faction` of the pair.
+/// relation --- The new `Relation` between the `Faction`s.
+///
+/// #Errors
+///
+/// SelfRelationError --- first == second
+/// TransitionError --- !can_transition(prior, relation)
+pub fn set_relation_checked(relations: &mut HashMap<FactionPair, Relation>, first: Faction,
+    second: Faction, relation: Relation) -> Result<Relation, RelationError> {
+    let pair = FactionPair::new(first, second).ok_or(SelfRelationError)?;
+    //Absent `Relation`s are `Unaware`.
+    let prior = relations.get(&pair).cloned().unwrap_or(Unaware);
+    
+    if can_transition(prior, relation) {
+        relations.insert(pair, relation);
+        Ok(prior)
+    } else {
+        Err(TransitionError)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +266,76 @@ mod tests {
             assert!(pair == Some(FactionPair::from_parts(0, 1)), "`FactionPair::new` failed to swap factions.");
         }
     }
+    
+    #[test]
+    fn test_relation_ordering() {
+        assert!(Enemy > Friendly, "`Relation` ordering failed to order by hostility.");
+        assert!(Friendly < Neutral && Neutral < Unaware && Unaware < Enemy, "`Relation` ordering is incorrect.");
+        assert!(::std::cmp::max(Friendly, Enemy) == Enemy, "`max` failed to return the most hostile `Relation`.");
+    }
+    
+    #[test]
+    fn test_count_by_relation() {
+        let mut relations = HashMap::new();
+        relations.insert(FactionPair::new(0, 1).unwrap(), Friendly);
+        relations.insert(FactionPair::new(0, 2).unwrap(), Enemy);
+        relations.insert(FactionPair::new(3, 0).unwrap(), Enemy);
+        relations.insert(FactionPair::new(0, 4).unwrap(), Unaware);
+        relations.insert(FactionPair::new(1, 2).unwrap(), Enemy);
+        
+        let counts = count_by_relation(&relations, 0);
+        assert!(counts.get(&Friendly) == Some(&1), "`count_by_relation` miscounted `Friendly` factions.");
+        assert!(counts.get(&Enemy) == Some(&2), "`count_by_relation` miscounted `Enemy` factions.");
+        assert!(counts.get(&Neutral) == None, "`count_by_relation` counted absent `Neutral` factions.");
+        assert!(counts.get(&Unaware) == None, "`count_by_relation` counted `Unaware` factions.");
+    }
+    
+    #[test]
+    fn test_set_relations() {
+        let mut relations = HashMap::new();
+        
+        let prior = set_relations(&mut relations, 0, vec![(1, Friendly), (2, Enemy), (0, Enemy), (3, Neutral)]);
+        assert!(
+            prior == vec![(1, Unaware), (2, Unaware), (3, Unaware)],
+            "`set_relations` returned incorrect prior `Relation`s."
+        );
+        assert!(
+            relations.get(&FactionPair::new(0, 2).unwrap()) == Some(&Enemy)
+            && relations.len() == 3,
+            "`set_relations` set incorrect `Relation`s."
+        );
+        
+        let prior = set_relations(&mut relations, 0, vec![(2, Neutral)]);
+        assert!(prior == vec![(2, Enemy)], "`set_relations` failed to return the overwritten `Relation`.");
+    }
+    
+    #[test]
+    fn test_set_relation_checked() {
+        let mut relations = HashMap::new();
+        
+        assert!(
+            set_relation_checked(&mut relations, 0, 1, Friendly) == Err(TransitionError)
+            && relations.is_empty(),
+            "`set_relation_checked` allowed an illegal transition."
+        );
+        assert!(set_relation_checked(&mut relations, 0, 1, Neutral) == Ok(Unaware), "`set_relation_checked` refused a legal transition.");
+        assert!(set_relation_checked(&mut relations, 1, 0, Friendly) == Ok(Neutral), "`set_relation_checked` refused a legal transition.");
+        assert!(set_relation_checked(&mut relations, 0, 2, Enemy) == Ok(Unaware), "`set_relation_checked` refused a declaration of war.");
+        assert!(set_relation_checked(&mut relations, 0, 0, Enemy) == Err(SelfRelationError), "`set_relation_checked` related a `Faction` to itself.");
+        assert!(!can_transition(Enemy, Unaware), "`can_transition` allowed `Faction`s to become `Unaware`.");
+    }
+    
+    #[test]
+    fn test_forget() {
+        let mut relations = HashMap::new();
+        relations.insert(FactionPair::new(0, 1).unwrap(), Enemy);
+        relations.insert(FactionPair::new(0, 2).unwrap(), Unaware);
+        
+        assert!(is_aware(&relations, 0, 1) && is_aware(&relations, 1, 0), "`is_aware` failed on a known `Faction`.");
+        assert!(!is_aware(&relations, 0, 2) && !is_aware(&relations, 0, 3), "`is_aware` failed on an unknown `Faction`.");
+        
+        assert!(forget(&mut relations, 1, 0) == Some(Enemy), "`forget` returned an incorrect prior `Relation`.");
+        assert!(!is_aware(&relations, 0, 1) && relations.len() == 1, "`forget` failed to remove the `Relation`.");
+        assert!(forget(&mut relations, 0, 1) == None, "`forget` removed an absent `Relation`.");
+    }
 }
@@ -0,0 +1,199 @@
+//! `file_system` defines helpers for accessing the resource files of the game.
+//!
+//! #Last Modified
+//!
+//! Author: Daniel Bechaz</br>
+//! Date: 2017/11/10
+
+use game::combat::ships::SHIPS_DIR;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{self, Read, Write};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// An error from reading or writing a `.toml` file.
+#[derive(Debug)]
+pub enum TomlFileError {
+    /// The file could not be read or written.
+    IoError(io::Error),
+    /// The content of the file could not be interpreted.
+    DeserializeError(::toml::de::Error),
+    /// The value could not be converted into `.toml` content.
+    SerializeError(::toml::ser::Error)
+}
+pub use self::TomlFileError::*;
+
+impl fmt::Display for TomlFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IoError(ref e) => e.fmt(f),
+            DeserializeError(ref e) => e.fmt(f),
+            SerializeError(ref e) => e.fmt(f)
+        }
+    }
+}
+
+impl From<io::Error> for TomlFileError {
+    fn from(e: io::Error) -> Self {
+        IoError(e)
+    }
+}
+
+impl From<::toml::de::Error> for TomlFileError {
+    fn from(e: ::toml::de::Error) -> Self {
+        DeserializeError(e)
+    }
+}
+
+impl From<::toml::ser::Error> for TomlFileError {
+    fn from(e: ::toml::ser::Error) -> Self {
+        SerializeError(e)
+    }
+}
+
+/// The directories, relative to the working directory, which resources are read from.
+pub static RESOURCE_DIRS: &[&str] = &[SHIPS_DIR];
+
+/// Creates any of the `RESOURCE_DIRS` which are missing from the working directory.
+pub fn ensure_resource_dirs() -> io::Result<()> {
+    ensure_resource_dirs_in(Path::new("."))
+}
+
+/// Creates any of the `RESOURCE_DIRS` which are missing from `root`.
+///
+/// #Params
+///
+/// root --- The directory the `RESOURCE_DIRS` are relative to.
+fn ensure_resource_dirs_in(root: &Path) -> io::Result<()> {
+    RESOURCE_DIRS.iter()
+    .map(|dir| fs::create_dir_all(root.join(dir)))
+    .collect()
+}
+
+/// Reads the content of a file as text.
+///
+/// #Params
+///
+/// file_path --- The path to the file to read.
+pub fn read_file(file_path: &Path) -> io::Result<String> {
+    let mut content = String::new();
+    fs::File::open(file_path)?.read_to_string(&mut content)?;
+    
+    Ok(content)
+}
+
+/// Reads a `.toml` file and interprets its content.
+///
+/// #Params
+///
+/// file_path --- The path to the `.toml` file to read.
+pub fn read_toml<T: DeserializeOwned>(file_path: &Path) -> Result<T, TomlFileError> {
+    //Read in the content of the file.
+    let content = read_file(file_path)?;
+    
+    //Interperate the content.
+    Ok(::toml::from_str(&content)?)
+}
+
+/// Writes `bytes` to a file, creating it if it does not exist and replacing any
+/// existing content.
+///
+/// #Params
+///
+/// file_path --- The path to the file to write.
+/// bytes --- The content to write.
+pub fn write_file(file_path: &Path, bytes: &[u8]) -> io::Result<()> {
+    fs::File::create(file_path)?.write_all(bytes)
+}
+
+/// Appends `bytes` to the end of a file, creating it if it does not exist.
+///
+/// #Params
+///
+/// file_path --- The path to the file to append to.
+/// bytes --- The content to append.
+pub fn append_file(file_path: &Path, bytes: &[u8]) -> io::Result<()> {
+    fs::OpenOptions::new()
+    .append(true)
+    .create(true)
+    .open(file_path)?
+    .write_all(bytes)
+}
+
+/// Writes `value` to a `.toml` file, replacing any existing content.
+///
+/// #Params
+///
+/// file_path --- The path to the `.toml` file to write.
+/// value --- The value to write.
+pub fn write_toml<T: Serialize>(file_path: &Path, value: &T) -> Result<(), TomlFileError> {
+    //Convert to a `Value` first so that values are ordered before tables.
+    let content = ::toml::Value::try_from(value)?.to_string();
+    
+    Ok(write_file(file_path, content.as_bytes())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::UInt;
+    
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestResource {
+        value: UInt
+    }
+    
+    #[test]
+    fn test_read_toml() {
+        let file_path = ::std::env::temp_dir().join("the_brass_test_read_toml.toml");
+        
+        fs::write(&file_path, "value = 5\n").expect("Failed to write file.");
+        let resource = read_toml::<TestResource>(&file_path);
+        assert!(
+            resource.expect("`read_toml` failed to read a valid file.") == TestResource { value: 5 },
+            "`read_toml` returned incorrect content."
+        );
+        
+        fs::write(&file_path, "value = \"five\"\n").expect("Failed to write file.");
+        match read_toml::<TestResource>(&file_path) {
+            Err(DeserializeError(_)) => (),
+            res => panic!("`read_toml` failed to error on invalid content: {:?}", res)
+        }
+        
+        fs::remove_file(&file_path).ok();
+        match read_toml::<TestResource>(&file_path) {
+            Err(IoError(_)) => (),
+            res => panic!("`read_toml` failed to error on a missing file: {:?}", res)
+        }
+    }
+    
+    #[test]
+    fn test_ensure_resource_dirs() {
+        let root = ::std::env::temp_dir().join("the_brass_test_ensure_resource_dirs");
+        fs::remove_dir_all(&root).ok();
+        
+        ensure_resource_dirs_in(&root).expect("`ensure_resource_dirs_in` failed to create directories.");
+        assert!(
+            RESOURCE_DIRS.iter().all(|dir| root.join(dir).is_dir()),
+            "`ensure_resource_dirs_in` failed to create all directories."
+        );
+        
+        ensure_resource_dirs_in(&root).expect("`ensure_resource_dirs_in` failed on existing directories.");
+        fs::remove_dir_all(&root).ok();
+    }
+    
+    #[test]
+    fn test_append_file() {
+        let file_path = ::std::env::temp_dir().join("the_brass_test_append_file.log");
+        fs::remove_file(&file_path).ok();
+        
+        append_file(&file_path, b"first\n").expect("`append_file` failed to create file.");
+        append_file(&file_path, b"second\n").expect("`append_file` failed to append to file.");
+        let content = fs::read_to_string(&file_path).expect("Failed to read file.");
+        fs::remove_file(&file_path).ok();
+        
+        assert!(content == "first\nsecond\n", "`append_file` failed to append content.");
+    }
+}
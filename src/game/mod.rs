@@ -5,8 +5,10 @@
 //! Author: Daniel Bechaz</br>
 //! Date: 2017/11/06
 
+pub mod clock;
 pub mod combat;
 pub mod factions;
+pub mod file_system;
 
 /// A type alias for the standard unsigned integer type used in the game.
 pub type UInt = u32;
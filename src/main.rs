@@ -12,15 +12,26 @@ extern crate serde_derive;
 
 use std::thread;
 use std::io;
-use std::sync::Mutex;
+use std::path::Path;
+use std::sync::{Mutex, Once, ONCE_INIT};
+use std::rc::Rc;
 
 mod game;
 
 use self::game::*;
 
 static mut STAY_ALIVE: bool = true;
+/// True if the Ships have changed since they were last saved or loaded.
+static mut DIRTY: bool = false;
 static mut ALL_SHIPS: *mut Mutex<Vec<factions::AllignedInstance<combat::ships::ReducedShip>>>
     = 0 as *mut Mutex<Vec<factions::AllignedInstance<combat::ships::ReducedShip>>>;
+static INIT_ALL_SHIPS: Once = ONCE_INIT;
+
+unsafe fn init_all_ships() {
+    INIT_ALL_SHIPS.call_once(
+        || ALL_SHIPS = Box::into_raw(Box::new(Mutex::new(Vec::new())))
+    )
+}
 
 macro_rules! get_all_ships {
     () => {
@@ -31,10 +42,14 @@ macro_rules! get_all_ships {
 }
 
 fn main() {
+    if let Err(e) = file_system::ensure_resource_dirs() {
+        eprintln!("Failed to create the resource directories:\n    {}", e);
+    }
+    
     unsafe {
         combat::ships::ship_template::init_game_templates();
         factions::init_game_factions();
-        ALL_SHIPS = Box::into_raw(Box::new(Mutex::new(Vec::new())))
+        init_all_ships();
     }
     
     let game_thread = thread::spawn(game_loop);
@@ -43,23 +58,78 @@ fn main() {
     game_thread.join().expect("Failed to join the `game_thread`.");
 }
 
+/// The commands recognised by `command_loop`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Command {
+    Kill,
+    SpawnShip,
+    KillShips,
+    DespawnShip,
+    Simulate,
+    ListShips,
+    LoadRoster,
+    Save,
+    Load,
+    Replay,
+    Reload,
+    Count,
+    Help,
+    Advance,
+    Time,
+    Unknown
+}
+
+/// Returns the `Command` named by the first word of a line, ignoring case.
+/// Commands which require arguments are `Unknown` without them.
+///
+/// #Params
+///
+/// line --- The line entered by the user.
+fn parse_command(line: &str) -> Command {
+    let mut words = line.splitn(2, ' ');
+    let name = words.next().unwrap().to_lowercase();
+    let has_args = words.next().is_some_and(|args| !args.trim().is_empty());
+    
+    match (name.as_str(), has_args) {
+        ("kill", _) => Command::Kill,
+        ("spawn_ship", true) => Command::SpawnShip,
+        ("kill_ships", _) => Command::KillShips,
+        ("despawn_ship", true) => Command::DespawnShip,
+        ("simulate", true) => Command::Simulate,
+        ("list_ships", _) => Command::ListShips,
+        ("load_roster", true) => Command::LoadRoster,
+        ("save", true) => Command::Save,
+        ("load", true) => Command::Load,
+        ("replay", true) => Command::Replay,
+        ("reload", true) => Command::Reload,
+        ("count", _) => Command::Count,
+        ("help", _) => Command::Help,
+        ("advance", _) => Command::Advance,
+        ("time", _) => Command::Time,
+        _ => Command::Unknown
+    }
+}
+
+/// Returns true if the program should terminate when `kill` is entered.
+///
+/// #Params
+///
+/// dirty --- True if there are unsaved changes to the Ships.
+/// force --- True if `--force` was passed to `kill`.
+fn should_exit(dirty: bool, force: bool) -> bool {
+    !dirty || force
+}
+
+/// Returns the number of groups of Ships which are spawned.
+fn ship_count() -> usize {
+    get_all_ships!().lock().unwrap().len()
+}
+
 fn command_loop() {
     loop {
         let mut line = String::with_capacity(255);
         if let Ok(_) = io::stdin().read_line(&mut line) {
-            line = line.trim().parse().unwrap();
-            
-            if line.split(' ').next().unwrap().to_lowercase() == "kill" {
-                unsafe {
-                    STAY_ALIVE = false;
-                }
-            } else if line.starts_with("spawn_ship ") {
-                spawn_ship(line)
-            } else if line.split(' ').next().unwrap().to_lowercase() == "kill_ships" {
-                get_all_ships!().lock().unwrap().clear();
-            } else {
-                print_help(line);
-            }
+            print!("{}", execute(line.trim().parse().unwrap()));
         }
         
         unsafe {
@@ -70,14 +140,204 @@ fn command_loop() {
     }
 }
 
-fn print_help(line: String) {
-    println!("Do not recognise command: \"{}\". Try:", line);
-    println!("    spawn_ship `typename` `faction` `quantity` --- Attempts to spawn Ships using the passed parameters.");
-    println!("                                    kill_ships --- Despawns all Ships.");
-    println!("                                          kill --- Terminates the program.");
+/// The maximum number of scripts which can be replayed within each other.
+const MAX_SCRIPT_DEPTH: usize = 8;
+
+/// Executes a single command and returns its output.
+///
+/// #Params
+///
+/// line --- The line entered by the user.
+fn execute(line: String) -> String {
+    execute_in_script(line, 0)
 }
 
-fn spawn_ship(line: String) {
+/// Executes a single command from within `depth` replayed scripts and returns its output.
+///
+/// #Params
+///
+/// line --- The line to execute.
+/// depth --- The number of scripts the command is being run within, 0 for the user.
+fn execute_in_script(line: String, depth: usize) -> String {
+    match parse_command(&line) {
+        //A script cannot end the session.
+        Command::Kill if depth > 0 => String::from("`kill` cannot be used in a script.\n"),
+        Command::Kill => unsafe {
+            let force = line.split(' ').skip(1).any(|arg| arg == "--force");
+            
+            if should_exit(DIRTY, force) {
+                STAY_ALIVE = false;
+                String::new()
+            } else {
+                String::from("There are unsaved changes to the Ships, use `kill --force` to terminate anyway.\n")
+            }
+        },
+        Command::SpawnShip => spawn_ship(line),
+        Command::KillShips => {
+            let mut all_ships = get_all_ships!().lock().unwrap();
+            
+            //Killing an empty roster changes nothing.
+            if !all_ships.is_empty() {
+                all_ships.clear();
+                unsafe {
+                    DIRTY = true;
+                }
+            }
+            String::new()
+        },
+        Command::DespawnShip => despawn_ship(line),
+        Command::Simulate => simulate(line),
+        Command::ListShips => list_ships(line),
+        Command::LoadRoster => {
+            let path = line["load_roster ".len()..].trim();
+            
+            match load_roster(path.as_ref()) {
+                Ok((ships, errors)) => {
+                    if !ships.is_empty() {
+                        get_all_ships!().lock().unwrap().extend(ships);
+                        unsafe {
+                            DIRTY = true;
+                        }
+                    }
+                    errors.iter().map(|e| format!("{}\n", e)).collect()
+                },
+                Err(e) => format!("Failed to load the roster \"{}\":\n    {}\n\n", path, e)
+            }
+        },
+        Command::Save => {
+            let path = line["save ".len()..].trim();
+            
+            match save_ships(&get_all_ships!().lock().unwrap(), path.as_ref()) {
+                Ok(()) => {
+                    unsafe {
+                        DIRTY = false;
+                    }
+                    String::new()
+                },
+                Err(e) => format!("Failed to save Ships to \"{}\":\n    {}\n\n", path, e)
+            }
+        },
+        Command::Load => {
+            let path = line["load ".len()..].trim();
+            
+            match load_ships(path.as_ref()) {
+                Ok(ships) => {
+                    *get_all_ships!().lock().unwrap() = ships;
+                    unsafe {
+                        DIRTY = false;
+                    }
+                    String::new()
+                },
+                Err(e) => format!("Failed to load Ships from \"{}\":\n    {}\n\n", path, e)
+            }
+        },
+        Command::Replay => {
+            let path = line["replay ".len()..].trim();
+            
+            match file_system::read_file(path.as_ref()) {
+                Ok(_) if depth >= MAX_SCRIPT_DEPTH => format!(
+                    "Failed to replay the script \"{}\":\n    scripts cannot be replayed more than {} deep\n\n",
+                    path, MAX_SCRIPT_DEPTH
+                ),
+                Ok(content) => run_script(&content.lines().collect::<Vec<_>>(), depth + 1).concat(),
+                Err(e) => format!("Failed to read the script \"{}\":\n    {}\n\n", path, e)
+            }
+        },
+        Command::Reload => {
+            //The type name is quoted as in `spawn_ship`.
+            let typename = String::from(line["reload ".len()..].trim().trim_matches('"'));
+            
+            match combat::ships::get_game_templates().reload(&typename) {
+                Ok(_) => String::new(),
+                Err(e) => format!("Failed to reload \"{}\":\n    {}\n\n", typename, e)
+            }
+        },
+        Command::Count => format!("{} groups of Ships.\n", ship_count()),
+        Command::Help => format_commands(),
+        Command::Advance => {
+            let periods = line["advance".len()..].trim();
+            //Without an argument a single period passes.
+            let parsed = if periods.is_empty() {
+                Ok(1)
+            } else {
+                periods.parse::<UInt>()
+            };
+            
+            match parsed {
+                Ok(periods) => {
+                    //True if any Ships were changed, Ships with full shields which use no
+                    //fuel are unchanged.
+                    let mut changed = false;
+                    
+                    for _ in 0..periods {
+                        //The Ships are locked for one period at a time so that other
+                        //commands are not blocked for the whole advance.
+                        let mut all_ships = get_all_ships!().lock().unwrap();
+                        
+                        changed |= advance_period(&mut all_ships, &mut clock::get_game_clock());
+                    }
+                    if changed {
+                        unsafe {
+                            DIRTY = true;
+                        }
+                    }
+                    String::new()
+                },
+                Err(_) => format!("`advance` expects a positive number as it's argument, got \"{}\".\n\n", periods)
+            }
+        },
+        Command::Time => format!("Period {}\n", clock::get_game_clock().current_period()),
+        Command::Unknown => format_help(line)
+    }
+}
+
+/// Executes each line of a script as a command and returns the output of each command.
+/// Empty lines and lines starting with `#` are skipped.
+///
+/// #Params
+///
+/// lines --- The lines of the script.
+/// depth --- The number of scripts being run, including this one.
+fn run_script(lines: &[&str], depth: usize) -> Vec<String> {
+    lines.iter()
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .map(|line| execute_in_script(String::from(line), depth))
+    .collect()
+}
+
+/// The usage and description of every command, in the order they are listed by `help`.
+const COMMANDS: &[(&str, &str)] = &[
+    ("spawn_ship `typename` `faction` `quantity`", "Attempts to spawn Ships using the passed parameters."),
+    ("despawn_ship `typename` `quantity`", "Despawns up to `quantity` Ships of a type, or all of them."),
+    ("kill_ships", "Despawns all Ships."),
+    ("list_ships `--color` `faction`", "Lists all Ships, or only those of `faction` with their totals, optionally colored by faction."),
+    ("simulate `typename` `damage`", "Shows the effect of `damage` on an undamaged Ship of a type."),
+    ("save `path`", "Saves all Ships to a file."),
+    ("load `path`", "Replaces all Ships with those saved in a file."),
+    ("load_roster `path`", "Spawns the `typename : quantity` lines of a file, named for its faction."),
+    ("replay `path`", "Runs each line of a file as a command, skipping lines starting with `#` and `kill`."),
+    ("reload `typename`", "Reloads a Ship type from its `.ship` file, spawned Ships keep the old type."),
+    ("count", "Shows the number of groups of Ships."),
+    ("advance `periods`", "Advances the game by `periods` periods, or one period."),
+    ("time", "Shows the current period."),
+    ("help", "Lists all commands."),
+    ("kill `--force`", "Terminates the program, `--force` discards unsaved changes.")
+];
+
+fn format_commands() -> String {
+    let width = COMMANDS.iter().map(|&(usage, _)| usage.len()).max().unwrap_or(0);
+    
+    COMMANDS.iter()
+    .map(|&(usage, description)| format!("    {:>width$} --- {}\n", usage, description, width = width))
+    .collect()
+}
+
+fn format_help(line: String) -> String {
+    format!("Do not recognise command: \"{}\". Try:\n{}", line, format_commands())
+}
+
+fn spawn_ship(line: String) -> String {
     let args = line.chars().skip("spawn_ship ".len());
     let chars = args.clone().skip(1).take_while(|c| *c != "\"".chars().next().unwrap());
     let mut typename = String::with_capacity(chars.size_hint().0);
@@ -99,15 +359,327 @@ fn spawn_ship(line: String) {
             1
         };
         
-        if let Some(factions::AllignedInstance(faction, ship)) = combat::ships::build_game_ship(&typename, faction) {
-            let mut all_ships = get_all_ships!().lock().unwrap();
-            all_ships.push(factions::AllignedInstance(faction, combat::ships::ReducedShip::new(ship, quantity)));
+        if let Some(ship) = combat::ships::build_game_ship(&typename, faction) {
+            //Ships are only spawned if the attacks of their type are valid.
+            match combat::ships::ReducedShip::try_new(ship.1, quantity) {
+                Ok(group) => {
+                    let mut all_ships = get_all_ships!().lock().unwrap();
+                    all_ships.push(factions::AllignedInstance(ship.0, group));
+                    unsafe {
+                        DIRTY = true;
+                    }
+                    String::new()
+                },
+                Err(e) => format!("`spawn_ship` cannot spawn \"{}\", it has an invalid attack:\n    {:?}\n\n", typename, e)
+            }
         } else {
-            println!("`spawn_ship` must have a valid type name as its first argument.\n");
+            String::from("`spawn_ship` must have a valid type name as its first argument.\n\n")
         }
     } else {
-        println!("`spawn_ship` expects a positive number as it's second argument, got \"{}\".\n", faction_string);
+        format!("`spawn_ship` expects a positive number as it's second argument, got \"{}\".\n\n", faction_string)
+    }
+}
+
+fn despawn_ship(line: String) -> String {
+    //The type name is quoted as in `spawn_ship`.
+    let args = line["despawn_ship ".len()..].trim().trim_start_matches('"');
+    let typename = args.split('"').next().unwrap();
+    let quantity = args[typename.len()..].trim_start_matches('"').trim();
+    
+    let quantity = if quantity.is_empty() {
+        UInt::MAX
+    } else if let Ok(quantity) = quantity.parse::<UInt>() {
+        quantity
+    } else {
+        return format!("`despawn_ship` expects a positive number as it's second argument, got \"{}\".\n\n", quantity);
+    };
+    
+    let despawned = despawn(&mut get_all_ships!().lock().unwrap(), typename, quantity);
+    if despawned != 0 {
+        unsafe {
+            DIRTY = true;
+        }
+    }
+    format!("Despawned {} \"{}\" Ships.\n\n", despawned, typename)
+}
+
+/// Removes up to `count` Ships of the passed type from `ships` and returns the number
+/// which were removed.
+/// Groups which have no Ships left are removed.
+///
+/// #Params
+///
+/// ships --- The Ships to remove from.
+/// typename --- The type name of the Ships to remove.
+/// count --- The maximum number of Ships to remove.
+fn despawn(ships: &mut Vec<factions::AllignedInstance<combat::ships::ReducedShip>>, typename: &str, mut count: UInt) -> UInt {
+    //The number of Ships which were removed.
+    let mut despawned = 0;
+    
+    for ship in ships.iter_mut()
+        .filter(|ship| ship.as_ref().template_name().is_some_and(|name| name == typename)) {
+        //Remove as many Ships from this group as are left to remove.
+        let removed = ::std::cmp::min(ship.1.number, count);
+        
+        ship.1.number -= removed;
+        count -= removed;
+        despawned += removed;
     }
+    //Drop the groups which were emptied.
+    ships.retain(|ship| ship.is_alive());
+    
+    despawned
+}
+
+fn simulate(line: String) -> String {
+    //The type name is quoted as in `spawn_ship`.
+    let args = line["simulate ".len()..].trim().trim_start_matches('"');
+    let typename = String::from(args.split('"').next().unwrap());
+    let damage = args[typename.len()..].trim_start_matches('"').trim();
+    
+    if let Ok(damage) = damage.parse::<combat::ships::DamagePoint>() {
+        if let Some(template) = combat::ships::get_game_templates().get(&typename) {
+            let (hull, shield, unused) = simulate_hit(template, damage);
+            format!("\"{}\" would be left with {} hull and {} shields, with {} damage unused.\n\n", typename, hull, shield, unused)
+        } else {
+            String::from("`simulate` must have a valid type name as its first argument.\n\n")
+        }
+    } else {
+        format!("`simulate` expects a positive number as it's second argument, got \"{}\".\n\n", damage)
+    }
+}
+
+/// Simulates `damage` against an undamaged Ship of the passed `ShipTemplate` and returns
+/// the remaining hull, shield and unused damage.
+///
+/// #Params
+///
+/// template --- The `ShipTemplate` of the Ship.
+/// damage --- The damage leveled against the Ship.
+fn simulate_hit(template: Rc<combat::ships::ShipTemplate>, damage: combat::ships::DamagePoint)
+    -> (combat::ships::HullPoint, combat::ships::ShieldPoint, combat::ships::DamagePoint) {
+    combat::ships::Ship::from(template).simulate_damage(damage)
+}
+
+/// Formats the passed Ships as a list with one group of Ships per line.
+///
+/// #Params
+///
+/// ships --- The Ships to list.
+/// colored --- True if each line should be colored by its `Faction` with ANSI codes.
+fn format_ship_list<'a, I>(ships: I, colored: bool) -> String
+    where I: IntoIterator<Item = &'a factions::AllignedInstance<combat::ships::ReducedShip>> {
+    ships.into_iter()
+    .map(|ship| if colored {
+        color_line(ship.0, format_ship(ship.0, ship))
+    } else {
+        format_ship(ship.0, ship)
+    })
+    .collect()
+}
+
+/// Formats a group of Ships as a line of a list.
+///
+/// #Params
+///
+/// faction --- The `Faction` the Ships are alligned with.
+/// ship --- The Ships to format.
+fn format_ship(faction: factions::Faction, ship: &combat::ships::ReducedShip) -> String {
+    format!(
+        "Faction {}: {} x \"{}\" ({})\n",
+        faction,
+        ship.number,
+        ship.as_ref().template_name().unwrap_or_else(|| String::from("Unknown")),
+        combat::ships::size_class_name(ship.as_ref().get_ship_size_class())
+    )
+}
+
+/// Returns the ANSI foreground color code of a `Faction`, stable for the `Faction`.
+///
+/// #Params
+///
+/// faction --- The `Faction` to color.
+fn faction_color(faction: &factions::Faction) -> u8 {
+    use std::hash::{Hash, Hasher};
+    use std::collections::hash_map::DefaultHasher;
+    
+    let mut hasher = DefaultHasher::new();
+    faction.hash(&mut hasher);
+    //Pick from red to cyan, skipping black and white which may match the background.
+    31 + (hasher.finish() % 6) as u8
+}
+
+/// Wraps a line of a list in the color of a `Faction`.
+///
+/// #Params
+///
+/// faction --- The `Faction` to color the line by.
+/// line --- The line to color, ending in a newline.
+fn color_line(faction: factions::Faction, line: String) -> String {
+    format!("\x1b[{}m{}\x1b[0m\n", faction_color(&faction), line.trim_end_matches('\n'))
+}
+
+fn list_ships(line: String) -> String {
+    let args = line["list_ships".len()..].trim();
+    //`--color` may be passed before the faction.
+    let (colored, faction_string) = match args.split_whitespace().next() {
+        Some("--color") => (true, args["--color".len()..].trim()),
+        _ => (false, args)
+    };
+    let all_ships = get_all_ships!().lock().unwrap();
+    
+    if faction_string.is_empty() {
+        format_ship_list(all_ships.iter(), colored)
+    } else if let Ok(faction) = faction_string.parse::<factions::Faction>() {
+        let list = format_ship_list(all_ships.iter().filter(|ship| ship.0 == faction), colored);
+        //Summarise the number of each type of Ship in the faction.
+        let totals = composition(&ships_of_faction(&all_ships, &faction)).into_iter()
+            .map(|(typename, count)| format!("{} x \"{}\"", count, typename))
+            .collect::<Vec<_>>();
+        
+        if totals.is_empty() {
+            list
+        } else {
+            format!("{}Total: {}\n", list, totals.join(", "))
+        }
+    } else {
+        format!("`list_ships` expects a positive number as it's argument, got \"{}\".\n\n", faction_string)
+    }
+}
+
+/// Returns the Ships in `ships` which are alligned with `faction`.
+///
+/// #Params
+///
+/// ships --- The Ships to filter.
+/// faction --- The `Faction` to keep the Ships of.
+fn ships_of_faction<'a>(ships: &'a [factions::AllignedInstance<combat::ships::ReducedShip>],
+    faction: &factions::Faction) -> Vec<&'a combat::ships::ReducedShip> {
+    ships.iter()
+    .filter(|ship| ship.0 == *faction)
+    .map(|ship| &ship.1)
+    .collect()
+}
+
+/// Returns the type name and total number of each type of Ship in `ships`, merging
+/// groups of the same type and sorted by type name.
+///
+/// #Params
+///
+/// ships --- The Ships to summarise, such as those returned by `ships_of_faction`.
+fn composition(ships: &[&combat::ships::ReducedShip]) -> Vec<(String, UInt)> {
+    let mut counts = ::std::collections::BTreeMap::new();
+    
+    for ship in ships {
+        let count = counts.entry(ship.as_ref().template_name().unwrap_or_else(|| String::from("Unknown")))
+            .or_insert(0 as UInt);
+        *count = count.saturating_add(ship.number);
+    }
+    
+    counts.into_iter().collect()
+}
+
+/// The saved form of all the spawned Ships.
+#[derive(Serialize, Deserialize)]
+struct Roster {
+    /// The spawned Ships.
+    ships: Vec<RosterEntry>
+}
+
+/// The saved form of an `AllignedInstance<ReducedShip>`.
+#[derive(Serialize, Deserialize)]
+struct RosterEntry {
+    /// The `Faction` the Ships are alligned with.
+    faction: factions::Faction,
+    /// The Ships.
+    ships: combat::ships::ReducedShip
+}
+
+/// Saves the passed Ships to a `.toml` file.
+///
+/// #Params
+///
+/// ships --- The Ships to save.
+/// path --- The path of the file to save to.
+fn save_ships(ships: &[factions::AllignedInstance<combat::ships::ReducedShip>], path: &Path) -> Result<(), file_system::TomlFileError> {
+    let roster = Roster {
+        ships: ships.iter()
+            .map(|ship| RosterEntry { faction: ship.0, ships: ship.1.clone() })
+            .collect()
+    };
+    
+    file_system::write_toml(path, &roster)
+}
+
+/// Loads Ships saved by `save_ships`.
+///
+/// #Params
+///
+/// path --- The path of the file to load from.
+fn load_ships(path: &Path) -> Result<Vec<factions::AllignedInstance<combat::ships::ReducedShip>>, file_system::TomlFileError> {
+    let roster: Roster = file_system::read_toml(path)?;
+    
+    Ok(roster.ships.into_iter()
+        .map(|entry| factions::AllignedInstance(entry.faction, entry.ships))
+        .collect())
+}
+
+/// Builds the Ships listed in a roster file.
+/// Each line of the roster is `typename : quantity` and the name of the file is the
+/// `Faction` of the Ships.
+/// Returns the Ships which were built and a message for each line which failed.
+///
+/// #Params
+///
+/// path --- The path of the roster file.
+fn load_roster(path: &Path) -> io::Result<(Vec<factions::AllignedInstance<combat::ships::ReducedShip>>, Vec<String>)> {
+    //The faction is the name of the file.
+    let faction = path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.parse::<factions::Faction>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the roster must be named for its faction"))?;
+    let content = file_system::read_file(path)?;
+    
+    let mut ships = Vec::new();
+    let mut errors = Vec::new();
+    
+    for (number, line) in content.lines().enumerate()
+        .filter(|&(_, line)| !line.trim().is_empty()) {
+        let mut parts = line.splitn(2, ':');
+        let typename = String::from(parts.next().unwrap().trim());
+        
+        match parts.next().map(|quantity| quantity.trim().parse::<UInt>()) {
+            Some(Ok(quantity)) => match combat::ships::build_game_ship(&typename, faction) {
+                Some(ship) => ships.push(ship.into_reduced(quantity)),
+                None => errors.push(format!("Line {}: \"{}\" is not a valid type name.", number + 1, typename))
+            },
+            _ => errors.push(format!("Line {}: expected `typename : quantity`, got \"{}\".", number + 1, line))
+        }
+    }
+    
+    Ok((ships, errors))
+}
+
+/// Advances the game by one period, regenerating shields and using fuel for every Ship,
+/// and returns true if any Ship was changed.
+///
+/// #Params
+///
+/// ships --- The Ships to advance.
+/// game_clock --- The `GameClock` to advance.
+fn advance_period(ships: &mut [factions::AllignedInstance<combat::ships::ReducedShip>], game_clock: &mut clock::GameClock) -> bool {
+    let mut changed = false;
+    
+    for ship in ships.iter_mut() {
+        //The shields and fuel of the Ship before the period passes.
+        let before = (ship.1.as_ref().get_shield_points(), ship.1.as_ref().get_fuel_units());
+        
+        ship.1.regenerate_shields();
+        ship.1.use_fuel();
+        changed |= before != (ship.1.as_ref().get_shield_points(), ship.1.as_ref().get_fuel_units());
+    }
+    game_clock.advance();
+    changed
 }
 
 fn game_loop() {
@@ -115,4 +687,378 @@ fn game_loop() {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use combat::ships::*;
+    
+    /// Held by tests which use the global Ships, templates or flags so that they do not
+    /// run at once.
+    static GLOBAL_SHIPS: Mutex<()> = Mutex::new(());
+    
+    #[test]
+    fn test_save_ships() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+        }
+        let template = get_game_templates().insert(
+            String::from("Test Roster Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let ships = vec![
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(template.clone()), 5)),
+            factions::AllignedInstance(1, ReducedShip::new(
+                Ship::new(template, 2, 50, 10).expect("Failed to create `Ship`."),
+                3
+            ))
+        ];
+        let path = ::std::env::temp_dir().join("the_brass_test_save_ships.toml");
+        
+        save_ships(&ships, &path).expect("`save_ships` failed to save Ships.");
+        let loaded = load_ships(&path).expect("`load_ships` failed to load Ships.");
+        ::std::fs::remove_file(&path).ok();
+        
+        assert!(loaded == ships, "`load_ships` did not load the saved Ships.");
+    }
+    
+    #[test]
+    fn test_despawn() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+        }
+        let template = get_game_templates().insert(
+            String::from("Test Despawn Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let other = get_game_templates().insert(
+            String::from("Test Despawn Other Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let mut ships = vec![
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(template.clone()), 3)),
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(other.clone()), 4)),
+            factions::AllignedInstance(1, ReducedShip::new(Ship::from(template.clone()), 2))
+        ];
+        
+        assert!(despawn(&mut ships, "Test Despawn Ship", 4) == 4, "`despawn` removed an incorrect number of Ships.");
+        assert!(
+            ships == vec![
+                factions::AllignedInstance(0, ReducedShip::new(Ship::from(other.clone()), 4)),
+                factions::AllignedInstance(1, ReducedShip::new(Ship::from(template.clone()), 1))
+            ],
+            "`despawn` failed to partially remove Ships."
+        );
+        
+        assert!(despawn(&mut ships, "Test Despawn Ship", 10) == 1, "`despawn` removed an incorrect number of Ships.");
+        assert!(
+            ships == vec![factions::AllignedInstance(0, ReducedShip::new(Ship::from(other), 4))],
+            "`despawn` failed to fully remove Ships."
+        );
+    }
+    
+    #[test]
+    fn test_ships_of_faction() {
+        let template = shared(test_template());
+        let ships = vec![
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(template.clone()), 1)),
+            factions::AllignedInstance(1, ReducedShip::new(Ship::from(template.clone()), 2)),
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(template.clone()), 3))
+        ];
+        
+        let filtered = ships_of_faction(&ships, &0);
+        assert!(
+            filtered.iter().map(|ship| ship.number).collect::<Vec<_>>() == vec![1, 3],
+            "`ships_of_faction` returned incorrect Ships."
+        );
+        assert!(ships_of_faction(&ships, &2).is_empty(), "`ships_of_faction` returned Ships of another faction.");
+    }
+    
+    #[test]
+    fn test_simulate_hit() {
+        let template = shared(test_template().shield_capacity(50));
+        
+        assert!(simulate_hit(template.clone(), 30) == (100, 20, 0), "`simulate_hit` failed to absorb damage with shields.");
+        assert!(simulate_hit(template.clone(), 80) == (70, 0, 0), "`simulate_hit` failed to damage hull.");
+        assert!(simulate_hit(template, 200) == (0, 0, 50), "`simulate_hit` failed to return overkill damage.");
+    }
+    
+    #[test]
+    fn test_load_roster() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+        }
+        let template = get_game_templates().insert(
+            String::from("Test Roster Fighter"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let other = get_game_templates().insert(
+            String::from("Test Roster Cruiser"),
+            test_template().ship_size_class(6).max_hull(500).build().expect("Failed to create template.")
+        );
+        let path = ::std::env::temp_dir().join("7.roster");
+        ::std::fs::write(&path, "Test Roster Fighter : 5\n\nTest Roster Cruiser : 2\nTest Roster Fighter 3\n")
+        .expect("Failed to write roster.");
+        
+        let (ships, errors) = load_roster(&path).expect("`load_roster` failed to load roster.");
+        ::std::fs::remove_file(&path).ok();
+        
+        assert!(
+            ships == vec![
+                factions::AllignedInstance(7, ReducedShip::new(Ship::from(template), 5)),
+                factions::AllignedInstance(7, ReducedShip::new(Ship::from(other), 2))
+            ],
+            "`load_roster` built incorrect Ships."
+        );
+        assert!(errors.len() == 1 && errors[0].starts_with("Line 4:"), "`load_roster` failed to report the malformed line.");
+    }
+    
+    #[test]
+    fn test_commands_documented() {
+        let dispatched = [
+            "kill", "spawn_ship", "kill_ships", "despawn_ship", "simulate", "list_ships",
+            "load_roster", "save", "load", "replay", "reload", "count", "help", "advance", "time"
+        ];
+        
+        for command in dispatched.iter() {
+            assert!(
+                COMMANDS.iter().any(|&(usage, _)| usage.split(' ').next() == Some(command)),
+                "`COMMANDS` does not document \"{}\".", command
+            );
+        }
+        for &(usage, _) in COMMANDS {
+            assert!(parse_command(usage) != Command::Unknown, "`COMMANDS` documents an unknown command \"{}\".", usage);
+        }
+    }
+    
+    #[test]
+    fn test_parse_command() {
+        for line in ["SPAWN_SHIP \"Fighter\" 0 1", "Spawn_Ship \"Fighter\" 0 1", "spawn_ship \"Fighter\" 0 1"].iter() {
+            assert!(parse_command(line) == Command::SpawnShip, "`parse_command` failed to parse \"{}\".", line);
+        }
+        assert!(parse_command("KILL") == Command::Kill, "`parse_command` failed to parse \"KILL\".");
+        assert!(parse_command("Kill_Ships") == Command::KillShips, "`parse_command` failed to parse \"Kill_Ships\".");
+        assert!(parse_command("save") == Command::Unknown, "`parse_command` accepted a command missing its arguments.");
+        assert!(parse_command("spawn") == Command::Unknown, "`parse_command` accepted an unknown command.");
+    }
+    
+    #[test]
+    fn test_should_exit() {
+        assert!(!should_exit(true, false), "`should_exit` exited with unsaved changes.");
+        assert!(should_exit(true, true), "`should_exit` ignored `--force`.");
+        assert!(should_exit(false, false), "`should_exit` refused to exit without changes.");
+    }
+    
+    #[test]
+    fn test_format_ship_list() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+        }
+        let template = get_game_templates().insert(
+            String::from("Test Format Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let ships = vec![
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(template.clone()), 5)),
+            factions::AllignedInstance(1, ReducedShip::new(Ship::from(template), 3))
+        ];
+        
+        let plain = format_ship_list(&ships, false);
+        assert!(!plain.contains('\x1b'), "`format_ship_list` added escape codes to plain output.");
+        assert!(
+            plain == "Faction 0: 5 x \"Test Format Ship\" (Fighter)\nFaction 1: 3 x \"Test Format Ship\" (Fighter)\n",
+            "`format_ship_list` returned incorrect plain output."
+        );
+        
+        let colored = format_ship_list(&ships, true);
+        assert!(
+            colored.starts_with(&format!("\x1b[{}m", faction_color(&0))) && colored.lines().count() == 2,
+            "`format_ship_list` returned incorrect colored output."
+        );
+        assert!((31..37).contains(&faction_color(&1)), "`faction_color` returned an invalid color.");
+    }
+    
+    #[test]
+    fn test_list_ships_color_flag() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_all_ships();
+        }
+        
+        assert!(
+            list_ships(String::from("list_ships --colorful")) == "`list_ships` expects a positive number as it's argument, got \"--colorful\".\n\n",
+            "`list_ships` accepted a flag starting with `--color`."
+        );
+        assert!(
+            !list_ships(String::from("list_ships --color 1")).contains("expects"),
+            "`list_ships` rejected `--color` before a faction."
+        );
+    }
+    
+    #[test]
+    fn test_composition() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+        }
+        let template = get_game_templates().insert(
+            String::from("Test Composition Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let other = get_game_templates().insert(
+            String::from("Test Composition Other Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        let ships = vec![
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(template.clone()), 3)),
+            factions::AllignedInstance(0, ReducedShip::new(Ship::from(other), 4)),
+            factions::AllignedInstance(0, ReducedShip::new(
+                Ship::new(template, 2, 50, 10).expect("Failed to create `Ship`."),
+                2
+            ))
+        ];
+        
+        assert!(
+            composition(&ships_of_faction(&ships, &0)) == vec![
+                (String::from("Test Composition Other Ship"), 4),
+                (String::from("Test Composition Ship"), 5)
+            ],
+            "`composition` failed to combine groups of the same type."
+        );
+        
+        unsafe {
+            init_all_ships();
+        }
+        //Set aside the Ships of other tests so that only these Ships are listed.
+        let saved = ::std::mem::replace(&mut *get_all_ships!().lock().unwrap(), ships);
+        let listed = list_ships(String::from("list_ships 0"));
+        *get_all_ships!().lock().unwrap() = saved;
+        
+        assert!(
+            listed.ends_with("Total: 4 x \"Test Composition Other Ship\", 5 x \"Test Composition Ship\"\n"),
+            "`list_ships` failed to summarise the composition of a faction."
+        );
+    }
+    
+    #[test]
+    fn test_advance_period() {
+        let template = shared(test_template().fuel_use(3).shield_recovery(5));
+        let mut ships = vec![factions::AllignedInstance(0, ReducedShip::new(
+            Ship::new(template, 10, 100, 90).expect("Failed to create `Ship`."),
+            2
+        ))];
+        let mut game_clock = clock::GameClock::new();
+        
+        assert!(
+            (0..4).all(|_| advance_period(&mut ships, &mut game_clock)),
+            "`advance_period` reported unchanged Ships."
+        );
+        assert!(game_clock.current_period() == 4, "`advance_period` failed to advance the clock.");
+        assert!(
+            ships[0].as_ref().get_shield_points() == 100 && ships[0].as_ref().get_fuel_units() == 0,
+            "`advance_period` failed to regenerate shields or use fuel."
+        );
+        assert!(!advance_period(&mut ships, &mut game_clock), "`advance_period` reported changes to unchanged Ships.");
+    }
+    
+    #[test]
+    fn test_run_script() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+            init_all_ships();
+        }
+        get_game_templates().insert(
+            String::from("Test Replay Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        
+        let output = run_script(&[
+            "# Spawn a group and list it.",
+            "spawn_ship \"Test Replay Ship\" 4 3",
+            "",
+            "list_ships 4",
+            "spawn_ship \"Test Missing Ship\" 4 3",
+            "kill"
+        ], 1);
+        get_all_ships!().lock().unwrap().retain(|ship| ship.0 != 4);
+        
+        assert!(
+            output == vec![
+                String::new(),
+                String::from("Faction 4: 3 x \"Test Replay Ship\" (Fighter)\nTotal: 3 x \"Test Replay Ship\"\n"),
+                String::from("`spawn_ship` must have a valid type name as its first argument.\n\n"),
+                String::from("`kill` cannot be used in a script.\n")
+            ],
+            "`run_script` returned incorrect output."
+        );
+        assert!(unsafe { STAY_ALIVE }, "`run_script` ended the session.");
+    }
+    
+    #[test]
+    fn test_replay_depth() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        let path = ::std::env::temp_dir().join("the_brass_test_replay_depth.script");
+        ::std::fs::write(&path, format!("replay {}\n", path.display())).expect("Failed to write script.");
+        
+        let output = execute(format!("replay {}", path.display()));
+        ::std::fs::remove_file(&path).ok();
+        
+        assert!(
+            output.contains(&format!("scripts cannot be replayed more than {} deep", MAX_SCRIPT_DEPTH)),
+            "`replay` failed to stop a script which replays itself."
+        );
+    }
+    
+    #[test]
+    fn test_ship_count() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+            init_all_ships();
+        }
+        get_game_templates().insert(
+            String::from("Test Count Ship"),
+            test_template().build().expect("Failed to create template.")
+        );
+        //Set aside the Ships of other tests so that the roster is empty.
+        let saved = ::std::mem::take(&mut *get_all_ships!().lock().unwrap());
+        
+        execute(String::from("spawn_ship \"Test Count Ship\" 0 3"));
+        execute(String::from("spawn_ship \"Test Count Ship\" 1 2"));
+        assert!(ship_count() == 2, "`ship_count` returned an incorrect count.");
+        
+        get_all_ships!().lock().unwrap().clear();
+        assert!(ship_count() == 0, "`ship_count` failed on no Ships.");
+        
+        *get_all_ships!().lock().unwrap() = saved;
+    }
+    
+    #[test]
+    fn test_dirty_tracking() {
+        let _lock = GLOBAL_SHIPS.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            init_game_templates();
+            init_all_ships();
+        }
+        //Set aside the Ships of other tests so that the roster is empty.
+        let saved = ::std::mem::take(&mut *get_all_ships!().lock().unwrap());
+        unsafe {
+            DIRTY = false;
+        }
+        
+        execute(String::from("spawn_ship \"Test Missing Ship\" 0 3"));
+        execute(String::from("despawn_ship \"Test Missing Ship\""));
+        execute(String::from("kill_ships"));
+        execute(String::from("advance"));
+        assert!(unsafe { !DIRTY }, "Commands which changed no Ships marked the Ships as changed.");
+        
+        assert!(execute(String::from("kill")) == "", "`kill` refused to exit after a failed spawn.");
+        unsafe {
+            STAY_ALIVE = true;
+        }
+        
+        *get_all_ships!().lock().unwrap() = saved;
+    }
 }